@@ -0,0 +1,46 @@
+package main
+
+import (
+	"strings"
+)
+
+// assetChoicesName is the cache file of the assets chosen by the user, as
+// asset filters by repo.
+const assetChoicesName = "assets.json"
+
+// assetFilters returns asset filters that select the asset named name in
+// other releases than version: the parts of name around the version, or the
+// whole name if it does not contain the version.
+func assetFilters(name, version string) []string {
+	for _, v := range []string{version, strings.TrimPrefix(version, "v")} {
+		if v == "" || !strings.Contains(name, v) {
+			continue
+		}
+		filters := []string{}
+		for _, part := range strings.Split(name, v) {
+			if part != "" {
+				filters = append(filters, part)
+			}
+		}
+		return filters
+	}
+	return []string{name}
+}
+
+// rememberAsset records the asset the user chose from several candidates of
+// the release version of repo, so installs and updates of repo select the
+// same kind of asset without asking.
+func rememberAsset(repo, name, version string) error {
+	choices := make(map[string][]string)
+	readCache(assetChoicesName, &choices)
+	choices[repoKey(repo)] = assetFilters(name, version)
+	return writeCache(assetChoicesName, choices)
+}
+
+// rememberedAsset returns the asset filters recorded for repo by
+// rememberAsset, or nil if the user never chose an asset of repo.
+func rememberedAsset(repo string) []string {
+	choices := make(map[string][]string)
+	readCache(assetChoicesName, &choices)
+	return choices[repoKey(repo)]
+}
@@ -0,0 +1,166 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+)
+
+const osvBatchURL = "https://api.osv.dev/v1/querybatch"
+
+// A Vulnerability is a tracked tool whose installed version is affected by
+// known advisories.
+type Vulnerability struct {
+	Tool
+	Advisories []string `json:"advisories"`
+}
+
+type osvPackage struct {
+	Name      string `json:"name"`
+	Ecosystem string `json:"ecosystem"`
+}
+
+type osvQuery struct {
+	Version string     `json:"version"`
+	Package osvPackage `json:"package"`
+}
+
+type osvBatchResult struct {
+	Results []struct {
+		Vulns []struct {
+			ID string `json:"id"`
+		} `json:"vulns"`
+	} `json:"results"`
+}
+
+// osvQueries returns the OSV queries for the installed version of t. Every
+// repo is looked up by its git tags, and GitHub repos are also looked up as
+// Go modules since those make up most of the advisories for CLI tools.
+func osvQueries(t Tool) []osvQuery {
+	u := repoURL(t.Repo)
+	if u == "" || t.Version == "" {
+		return nil
+	}
+	queries := []osvQuery{{
+		Version: t.Version,
+		Package: osvPackage{Name: u, Ecosystem: "GIT"},
+	}}
+	if strings.HasPrefix(u, "https://github.com/") {
+		queries = append(queries, osvQuery{
+			Version: "v" + strings.TrimPrefix(t.Version, "v"),
+			Package: osvPackage{Name: strings.TrimPrefix(u, "https://"), Ecosystem: "Go"},
+		})
+	}
+	return queries
+}
+
+// Audit queries the OSV database for advisories affecting the installed
+// version of each tool, and returns the affected tools. Tools whose version
+// was not recorded, or that were not installed from a release, cannot be
+// audited and are returned separately.
+func Audit(tools []Tool) ([]Vulnerability, []Tool, error) {
+	queries := []osvQuery{}
+	owners := []int{} // index of the tool each query is for
+	skipped := []Tool{}
+	for i, t := range tools {
+		if t.Status != StatusInstalled {
+			continue
+		}
+		qs := osvQueries(t)
+		if len(qs) == 0 {
+			skipped = append(skipped, t)
+			continue
+		}
+		for _, q := range qs {
+			queries = append(queries, q)
+			owners = append(owners, i)
+		}
+	}
+	vulns := []Vulnerability{}
+	if len(queries) == 0 {
+		return vulns, skipped, nil
+	}
+
+	data, err := json.Marshal(map[string][]osvQuery{"queries": queries})
+	if err != nil {
+		return nil, nil, err
+	}
+	resp, err := Post(osvBatchURL, "application/json", bytes.NewReader(data))
+	if err != nil {
+		return nil, nil, err
+	}
+	defer resp.Body.Close()
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, nil, err
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, nil, fmt.Errorf("osv: %s (URL: %s)", resp.Status, osvBatchURL)
+	}
+
+	var result osvBatchResult
+	if err := json.Unmarshal(body, &result); err != nil {
+		return nil, nil, fmt.Errorf("osv: %w", err)
+	}
+
+	// the same advisory may be found through several queries for one tool
+	byTool := make(map[int]*Vulnerability)
+	order := []int{}
+	for qi, r := range result.Results {
+		if qi >= len(owners) || len(r.Vulns) == 0 {
+			continue
+		}
+		ti := owners[qi]
+		v, ok := byTool[ti]
+		if !ok {
+			v = &Vulnerability{Tool: tools[ti]}
+			byTool[ti] = v
+			order = append(order, ti)
+		}
+		for _, a := range r.Vulns {
+			if !containsString(v.Advisories, a.ID) {
+				v.Advisories = append(v.Advisories, a.ID)
+			}
+		}
+	}
+	for _, ti := range order {
+		vulns = append(vulns, *byTool[ti])
+	}
+	return vulns, skipped, nil
+}
+
+func containsString(list []string, s string) bool {
+	for _, l := range list {
+		if l == s {
+			return true
+		}
+	}
+	return false
+}
+
+// PrintVulnerabilities writes the audit results to w in the given format:
+// "table" (the default), "json" or "porcelain".
+func PrintVulnerabilities(w io.Writer, vulns []Vulnerability, format string) error {
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2)
+		fmt.Fprintln(tw, "NAME\tREPO\tVERSION\tADVISORIES")
+		for _, v := range vulns {
+			fmt.Fprintf(tw, "%s\t%s\t%s\t%s\n", v.Name, displayRepo(v.Tool), v.Version, strings.Join(v.Advisories, ", "))
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(vulns)
+	case "porcelain":
+		for _, v := range vulns {
+			writePorcelain(w, v.Name, v.Repo, v.Path, v.Version, strings.Join(v.Advisories, ","))
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
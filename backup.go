@@ -0,0 +1,125 @@
+package main
+
+import (
+	"archive/tar"
+	"compress/gzip"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"time"
+)
+
+// Names of the files inside a backup archive.
+const (
+	backupLog    = "eget.log"
+	backupConfig = "eget.toml"
+)
+
+// Backup writes the log and the config file that was loaded, if any, to a
+// gzipped tar archive at path.
+func Backup(path string, config *Config) error {
+	logFile, err := GetLogFilePath()
+	if err != nil {
+		return err
+	}
+	files := []struct {
+		name string
+		src  string
+	}{
+		{backupLog, logFile},
+		{backupConfig, config.Meta.Path},
+	}
+
+	f, err := os.Create(path)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+
+	gz := gzip.NewWriter(f)
+	tw := tar.NewWriter(gz)
+	for _, file := range files {
+		if file.src == "" {
+			continue
+		}
+		data, err := os.ReadFile(file.src)
+		if os.IsNotExist(err) {
+			continue
+		} else if err != nil {
+			return err
+		}
+
+		hdr := &tar.Header{
+			Name:    file.name,
+			Mode:    0644,
+			Size:    int64(len(data)),
+			ModTime: time.Now(),
+		}
+		if err := tw.WriteHeader(hdr); err != nil {
+			return err
+		}
+		if _, err := tw.Write(data); err != nil {
+			return err
+		}
+	}
+	if err := tw.Close(); err != nil {
+		return err
+	}
+	return gz.Close()
+}
+
+// Restore replaces the log, and the config file at configPath, with the
+// contents of a backup created by Backup. It returns the paths that were
+// written.
+func Restore(path, configPath string) ([]string, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+
+	gz, err := gzip.NewReader(f)
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", path, err)
+	}
+	tr := tar.NewReader(gz)
+
+	restored := []string{}
+	for {
+		hdr, err := tr.Next()
+		if err == io.EOF {
+			break
+		} else if err != nil {
+			return restored, fmt.Errorf("%s: %w", path, err)
+		}
+
+		var dest string
+		switch hdr.Name {
+		case backupLog:
+			if err := ensureLogDir(); err != nil {
+				return restored, err
+			}
+			if dest, err = GetLogFilePath(); err != nil {
+				return restored, err
+			}
+		case backupConfig:
+			dest = configPath
+			if err := os.MkdirAll(filepath.Dir(dest), 0755); err != nil {
+				return restored, err
+			}
+		default:
+			continue
+		}
+
+		data, err := io.ReadAll(tr)
+		if err != nil {
+			return restored, err
+		}
+		if err := os.WriteFile(dest, data, 0644); err != nil {
+			return restored, err
+		}
+		restored = append(restored, dest)
+	}
+	return restored, nil
+}
@@ -0,0 +1,105 @@
+package main
+
+import (
+	"debug/elf"
+	"debug/macho"
+	"debug/pe"
+	"os"
+	"path/filepath"
+	"runtime"
+)
+
+var elfArchs = map[elf.Machine]string{
+	elf.EM_X86_64:  "amd64",
+	elf.EM_386:     "386",
+	elf.EM_AARCH64: "arm64",
+	elf.EM_ARM:     "arm",
+	elf.EM_RISCV:   "riscv64",
+}
+
+var machoArchs = map[macho.Cpu]string{
+	macho.CpuAmd64: "amd64",
+	macho.Cpu386:   "386",
+	macho.CpuArm64: "arm64",
+	macho.CpuArm:   "arm",
+}
+
+var peArchs = map[uint16]string{
+	pe.IMAGE_FILE_MACHINE_AMD64: "amd64",
+	pe.IMAGE_FILE_MACHINE_I386:  "386",
+	pe.IMAGE_FILE_MACHINE_ARM64: "arm64",
+	pe.IMAGE_FILE_MACHINE_ARMNT: "arm",
+}
+
+// binaryArchs returns the architectures, as GOARCH names, the executable at
+// path is built for: several for a macOS universal binary, none if the file
+// is not an ELF, Mach-O or PE executable or its architecture is unknown.
+func binaryArchs(path string) []string {
+	if f, err := elf.Open(path); err == nil {
+		defer f.Close()
+		if a, ok := elfArchs[f.Machine]; ok {
+			return []string{a}
+		}
+		return nil
+	}
+	if f, err := macho.OpenFat(path); err == nil {
+		defer f.Close()
+		archs := []string{}
+		for _, a := range f.Arches {
+			if name, ok := machoArchs[a.Cpu]; ok {
+				archs = append(archs, name)
+			}
+		}
+		return archs
+	}
+	if f, err := macho.Open(path); err == nil {
+		defer f.Close()
+		if a, ok := machoArchs[f.Cpu]; ok {
+			return []string{a}
+		}
+		return nil
+	}
+	if f, err := pe.Open(path); err == nil {
+		defer f.Close()
+		if a, ok := peArchs[f.Machine]; ok {
+			return []string{a}
+		}
+	}
+	return nil
+}
+
+// canRun returns true if this system can run binaries built for arch,
+// natively or through emulation: 32-bit builds on their 64-bit counterparts,
+// Rosetta on macOS, the x64 emulation of Windows on Arm, and qemu registered
+// with binfmt_misc on Linux.
+func canRun(arch string) bool {
+	host := runtime.GOARCH
+	switch {
+	case arch == host:
+		return true
+	case host == "amd64" && arch == "386", host == "arm64" && arch == "arm" && runtime.GOOS == "linux":
+		return true
+	case host == "arm64" && arch == "amd64" && runtime.GOOS == "darwin":
+		_, err := os.Stat("/Library/Apple/usr/libexec/oah/libRosettaRuntime")
+		return err == nil
+	case host == "arm64" && (arch == "amd64" || arch == "386") && runtime.GOOS == "windows":
+		return true
+	case runtime.GOOS == "linux":
+		qemu := map[string]string{"amd64": "x86_64", "386": "i386", "arm64": "aarch64", "arm": "arm", "riscv64": "riscv64"}[arch]
+		_, err := os.Stat(filepath.Join("/proc/sys/fs/binfmt_misc", "qemu-"+qemu))
+		return qemu != "" && err == nil
+	}
+	return false
+}
+
+// archMismatch returns the architectures of the binary at path if this system
+// cannot run any of them, or nil if it can or they are unknown.
+func archMismatch(path string) []string {
+	archs := binaryArchs(path)
+	for _, a := range archs {
+		if canRun(a) {
+			return nil
+		}
+	}
+	return archs
+}
@@ -0,0 +1,50 @@
+package main
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+)
+
+// GetCacheDir returns the directory eget caches data fetched from the network
+// in. It is always on this system, also with --root, so downloads are shared
+// between roots.
+func GetCacheDir() (string, error) {
+	dir, err := dataDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(dir, "cache"), nil
+}
+
+// readCache decodes the json cache file with the given name into v. A missing
+// or unreadable cache leaves v untouched.
+func readCache(name string, v interface{}) {
+	dir, err := GetCacheDir()
+	if err != nil {
+		return
+	}
+	data, err := os.ReadFile(filepath.Join(dir, name))
+	if err != nil {
+		return
+	}
+	json.Unmarshal(data, v)
+}
+
+// writeCache encodes v as json into the cache file with the given name,
+// which may be in a subdirectory of the cache.
+func writeCache(name string, v interface{}) error {
+	dir, err := GetCacheDir()
+	if err != nil {
+		return err
+	}
+	path := filepath.Join(dir, name)
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return err
+	}
+	data, err := json.Marshal(v)
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(path, data, 0644)
+}
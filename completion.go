@@ -0,0 +1,107 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"reflect"
+	"strings"
+)
+
+// A cliOption describes a command line flag, read from the tags on CliFlags.
+type cliOption struct {
+	Short       string
+	Long        string
+	Description string
+	TakesArg    bool
+}
+
+// cliOptions returns every flag accepted by eget.
+func cliOptions() []cliOption {
+	t := reflect.TypeOf(CliFlags{})
+	options := make([]cliOption, 0, t.NumField())
+	for i := 0; i < t.NumField(); i++ {
+		field := t.Field(i)
+		long := field.Tag.Get("long")
+		if long == "" {
+			continue
+		}
+		kind := field.Type.Kind()
+		if kind == reflect.Ptr {
+			kind = field.Type.Elem().Kind()
+		}
+		options = append(options, cliOption{
+			Short:       field.Tag.Get("short"),
+			Long:        long,
+			Description: field.Tag.Get("description"),
+			TakesArg:    kind != reflect.Bool,
+		})
+	}
+	return options
+}
+
+// WriteCompletion writes a completion script for the given shell (bash, zsh,
+// fish or powershell) to w.
+func WriteCompletion(w io.Writer, shell string) error {
+	options := cliOptions()
+	switch shell {
+	case "bash":
+		words := make([]string, 0, len(options))
+		for _, o := range options {
+			words = append(words, "--"+o.Long)
+		}
+		fmt.Fprintf(w, `_eget() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "%s" -- "$cur"))
+    else
+        COMPREPLY=($(compgen -f -- "$cur"))
+    fi
+}
+complete -F _eget eget
+`, strings.Join(words, " "))
+	case "zsh":
+		escape := strings.NewReplacer("'", `'\''`, "[", `\[`, "]", `\]`, ":", `\:`)
+		fmt.Fprintln(w, "#compdef eget")
+		fmt.Fprintln(w, "_arguments \\")
+		for _, o := range options {
+			arg := ""
+			if o.TakesArg {
+				arg = ":" + o.Long + ":"
+			}
+			desc := escape.Replace(o.Description)
+			if o.Short != "" {
+				fmt.Fprintf(w, "  '(-%s --%s)'{-%s,--%s}'[%s]%s' \\\n", o.Short, o.Long, o.Short, o.Long, desc, arg)
+			} else {
+				fmt.Fprintf(w, "  '--%s[%s]%s' \\\n", o.Long, desc, arg)
+			}
+		}
+		fmt.Fprintln(w, "  '*:target:_files'")
+	case "fish":
+		escape := strings.NewReplacer("'", `\'`)
+		for _, o := range options {
+			fmt.Fprintf(w, "complete -c eget -l %s", o.Long)
+			if o.Short != "" {
+				fmt.Fprintf(w, " -s %s", o.Short)
+			}
+			if o.TakesArg {
+				fmt.Fprint(w, " -r")
+			}
+			fmt.Fprintf(w, " -d '%s'\n", escape.Replace(o.Description))
+		}
+	case "powershell":
+		words := make([]string, 0, len(options))
+		for _, o := range options {
+			words = append(words, "'--"+o.Long+"'")
+		}
+		fmt.Fprintf(w, `Register-ArgumentCompleter -Native -CommandName eget -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+    @(%s) | Where-Object { $_ -like "$wordToComplete*" } | ForEach-Object {
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterName', $_)
+    }
+}
+`, strings.Join(words, ", "))
+	default:
+		return fmt.Errorf("unknown shell '%s' (must be bash, zsh, fish or powershell)", shell)
+	}
+	return nil
+}
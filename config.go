@@ -13,16 +13,30 @@ import (
 )
 
 type ConfigGlobal struct {
-	All          bool   `toml:"all"`
-	DownloadOnly bool   `toml:"download_only"`
-	File         string `toml:"file"`
-	GithubToken  string `toml:"github_token"`
-	Quiet        bool   `toml:"quiet"`
-	ShowHash     bool   `toml:"show_hash"`
-	Source       bool   `toml:"download_source"`
-	System       string `toml:"system"`
-	Target       string `toml:"target"`
-	UpgradeOnly  bool   `toml:"upgrade_only"`
+	All          bool     `toml:"all"`
+	CAFile       string   `toml:"ca_file"`
+	DLCache      string   `toml:"download_cache"`
+	DownloadOnly bool     `toml:"download_only"`
+	File         string   `toml:"file"`
+	GiteaHosts   []string `toml:"gitea_hosts"`
+	GithubToken  string   `toml:"github_token"`
+	Quarantine   bool     `toml:"keep_quarantine"`
+	LimitRate    string   `toml:"limit_rate"`
+	MaxDownloads int      `toml:"max_downloads"`
+	MaxRequests  int      `toml:"max_requests"`
+	Notify       bool     `toml:"notify"`
+	Permanent    bool     `toml:"permanent_delete"`
+	Quiet        bool     `toml:"quiet"`
+	ShowHash     bool     `toml:"show_hash"`
+	SkipManaged  bool     `toml:"skip_version_managed"`
+	Source       bool     `toml:"download_source"`
+	System       string   `toml:"system"`
+	Target       string   `toml:"target"`
+	UpgradeOnly  bool     `toml:"upgrade_only"`
+	Provenance   bool     `toml:"verify_provenance"`
+	SyncRepo     string   `toml:"sync_repo"`
+	SyncGist     string   `toml:"sync_gist"`
+	SyncFile     string   `toml:"sync_file"`
 }
 
 type ConfigRepository struct {
@@ -31,6 +45,7 @@ type ConfigRepository struct {
 	DownloadOnly bool     `toml:"download_only"`
 	File         string   `toml:"file"`
 	Name         string   `toml:"name"`
+	PublicKey    string   `toml:"public_key"`
 	Quiet        bool     `toml:"quiet"`
 	ShowHash     bool     `toml:"show_hash"`
 	Source       bool     `toml:"download_source"`
@@ -40,12 +55,14 @@ type ConfigRepository struct {
 	UpgradeOnly  bool     `toml:"upgrade_only"`
 	Verify       string   `toml:"verify_sha256"`
 	DisableSSL   bool     `toml:"disable_ssl"`
+	Provenance   bool     `toml:"verify_provenance"`
 }
 
 type Config struct {
 	Meta struct {
 		Keys     []string
 		MetaData *toml.MetaData
+		Path     string // file the configuration was loaded from
 	}
 	Global       ConfigGlobal `toml:"global"`
 	Repositories map[string]ConfigRepository
@@ -68,6 +85,7 @@ func LoadConfigurationFile(path string) (Config, error) {
 	}
 
 	conf.Meta.MetaData = &meta
+	conf.Meta.Path = path
 
 	return conf, err
 }
@@ -208,12 +226,41 @@ func InitializeConfig() (*Config, error) {
 			repo.Source = config.Global.Source
 		}
 
+		if !config.Meta.MetaData.IsDefined(name, "verify_provenance") {
+			repo.Provenance = config.Global.Provenance
+		}
+
 		config.Repositories[name] = repo
 	}
 
 	return &config, nil
 }
 
+// repoConfig returns the section of the config file for repo, matching any
+// spelling of the repo such as a URL or different case.
+func repoConfig(config *Config, repo string) (ConfigRepository, bool) {
+	if r, ok := config.Repositories[repo]; ok {
+		return r, true
+	}
+	for name, r := range config.Repositories {
+		if repoKey(name) == repoKey(repo) {
+			return r, true
+		}
+	}
+	return ConfigRepository{}, false
+}
+
+// defaultGiteaHosts are the Gitea and Forgejo instances known to eget
+// without any configuration.
+var defaultGiteaHosts = []string{"https://codeberg.org"}
+
+// giteaHosts returns the base URLs of the Gitea and Forgejo instances eget
+// looks up releases on: the defaults followed by those in the config file.
+func giteaHosts(config *Config) []string {
+	hosts := append([]string{}, defaultGiteaHosts...)
+	return append(hosts, config.Global.GiteaHosts...)
+}
+
 func update[T any](config T, cli *T) T {
 	if cli == nil {
 		return config
@@ -227,6 +274,16 @@ func SetGlobalOptionsFromConfig(config *Config, parser *flags.Parser, opts *Flag
 		os.Setenv("EGET_GITHUB_TOKEN", config.Global.GithubToken)
 	}
 
+	caFile := config.Global.CAFile
+	if env := os.Getenv("EGET_CA_FILE"); env != "" {
+		caFile = env
+	}
+	if caFile != "" {
+		if err := LoadRootCAs(caFile); err != nil {
+			return err
+		}
+	}
+
 	opts.Tag = update("", cli.Tag)
 	opts.Prerelease = update(false, cli.Prerelease)
 	opts.Source = update(config.Global.Source, cli.Source)
@@ -247,32 +304,68 @@ func SetGlobalOptionsFromConfig(config *Config, parser *flags.Parser, opts *Flag
 	opts.Verify = update("", cli.Verify)
 	opts.Remove = update(false, cli.Remove)
 	opts.DisableSSL = update(false, cli.DisableSSL)
+	opts.Provenance = update(config.Global.Provenance, cli.Provenance)
+	opts.GiteaHosts = giteaHosts(config)
+	opts.Offline = cli.Offline
+	if cli.Root != "" {
+		root, err := filepath.Abs(cli.Root)
+		if err != nil {
+			return err
+		}
+		if !IsDirectory(root) {
+			return fmt.Errorf("root %s is not a directory", root)
+		}
+		opts.Root = root
+	}
+	opts.Notify = update(config.Global.Notify, cli.Notify)
+	opts.Permanent = update(config.Global.Permanent, cli.Permanent)
+	opts.Quarantine = config.Global.Quarantine
+	if config.Global.MaxRequests < 0 || config.Global.MaxDownloads < 0 {
+		return fmt.Errorf("max_requests and max_downloads must be positive")
+	}
+	opts.MaxRequests = defaultMaxRequests
+	if config.Global.MaxRequests > 0 {
+		opts.MaxRequests = config.Global.MaxRequests
+	}
+	if opts.DLCache, err = home.Expand(config.Global.DLCache); err != nil {
+		return err
+	}
+	opts.MaxDownloads = 1
+	if config.Global.MaxDownloads > 0 {
+		opts.MaxDownloads = config.Global.MaxDownloads
+	}
+	if opts.LimitRate, err = parseRate(update(config.Global.LimitRate, cli.LimitRate)); err != nil {
+		return err
+	}
 	return nil
 }
 
-// Move the loaded configuration file project options into the opts variable
+// Move the loaded configuration file project options into the opts variable.
+// The section of the project is found with repoConfig, so it applies to any
+// spelling of the repo, such as the normalized one --update-all passes.
 func SetProjectOptionsFromConfig(config *Config, parser *flags.Parser, opts *Flags, cli CliFlags, projectName string) error {
-	for name, repo := range config.Repositories {
-		if name == projectName {
-			opts.All = update(repo.All, cli.All)
-			opts.Asset = update(repo.AssetFilters, cli.Asset)
-			opts.DLOnly = update(repo.DownloadOnly, cli.DLOnly)
-			opts.ExtractFile = update(repo.File, cli.ExtractFile)
-			opts.Hash = update(repo.ShowHash, cli.Hash)
-			targ, err := home.Expand(repo.Target)
-			if err != nil {
-				return err
-			}
-			opts.Output = update(targ, cli.Output)
-			opts.Quiet = update(repo.Quiet, cli.Quiet)
-			opts.Source = update(repo.Source, cli.Source)
-			opts.System = update(repo.System, cli.System)
-			opts.Tag = update(repo.Tag, cli.Tag)
-			opts.UpgradeOnly = update(repo.UpgradeOnly, cli.UpgradeOnly)
-			opts.Verify = update(repo.Verify, cli.Verify)
-			opts.DisableSSL = update(repo.DisableSSL, cli.DisableSSL)
-			break
-		}
+	repo, ok := repoConfig(config, projectName)
+	if !ok {
+		return nil
+	}
+	opts.All = update(repo.All, cli.All)
+	opts.Asset = update(repo.AssetFilters, cli.Asset)
+	opts.DLOnly = update(repo.DownloadOnly, cli.DLOnly)
+	opts.ExtractFile = update(repo.File, cli.ExtractFile)
+	opts.Hash = update(repo.ShowHash, cli.Hash)
+	targ, err := home.Expand(repo.Target)
+	if err != nil {
+		return err
 	}
+	opts.Output = update(targ, cli.Output)
+	opts.Quiet = update(repo.Quiet, cli.Quiet)
+	opts.Source = update(repo.Source, cli.Source)
+	opts.System = update(repo.System, cli.System)
+	opts.Tag = update(repo.Tag, cli.Tag)
+	opts.UpgradeOnly = update(repo.UpgradeOnly, cli.UpgradeOnly)
+	opts.Verify = update(repo.Verify, cli.Verify)
+	opts.DisableSSL = update(repo.DisableSSL, cli.DisableSSL)
+	opts.PublicKey = repo.PublicKey
+	opts.Provenance = update(repo.Provenance, cli.Provenance)
 	return nil
 }
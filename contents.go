@@ -0,0 +1,145 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path"
+	"path/filepath"
+	"strings"
+)
+
+// ErrNotCached is returned for tools whose release asset is not in the
+// download cache.
+var ErrNotCached = errors.New("the release asset is not in the download cache")
+
+// An ArchiveEntry is a file in the release asset a tool was installed from.
+type ArchiveEntry struct {
+	Name      string `json:"name"`
+	Mode      string `json:"mode"`
+	Link      string `json:"link,omitempty"`      // target of a link
+	Installed bool   `json:"installed,omitempty"` // the tool was installed from this file
+}
+
+// assetMatches returns true if the asset downloaded from url is the release
+// asset t was installed from.
+func assetMatches(t Tool, url string) bool {
+	if t.Source == SourceURL {
+		return url == t.Repo
+	}
+	project := strings.ToLower(strings.TrimPrefix(repoURL(t.Repo), "https://"))
+	if t.Version == "" || project == "" {
+		return false
+	}
+	// release assets are served from a URL with the project path and tag,
+	// such as https://github.com/user/repo/releases/download/v1.0/asset.tar.gz
+	return strings.Contains(strings.ToLower(url), project+"/") && strings.Contains(url, "/"+t.Version+"/")
+}
+
+// CachedAsset returns the URL of the release asset t was installed from and
+// the path of its copy in the download cache. When several assets of the
+// release were downloaded, the most recently used one is returned.
+func CachedAsset(t Tool) (string, string, error) {
+	dir, err := downloadCacheDir()
+	if err != nil {
+		return "", "", err
+	}
+	metas, _ := filepath.Glob(filepath.Join(dir, "*.json"))
+	var url, file string
+	var newest os.FileInfo
+	for _, m := range metas {
+		f := strings.TrimSuffix(m, ".json")
+		fi, err := os.Stat(f)
+		if err != nil || (newest != nil && fi.ModTime().Before(newest.ModTime())) {
+			continue
+		}
+		var meta cachedDownload
+		if data, err := os.ReadFile(m); err != nil || json.Unmarshal(data, &meta) != nil {
+			continue
+		}
+		if meta.SHA256 != "" && assetMatches(t, meta.URL) {
+			url, file, newest = meta.URL, f, fi
+		}
+	}
+	if url == "" {
+		return "", "", ErrNotCached
+	}
+	return url, file, nil
+}
+
+// ArchiveContents lists the files in the cached release asset of t, marking
+// the one t was installed from. An asset that is not an archive is listed as
+// a single file.
+func ArchiveContents(t Tool) (string, []ArchiveEntry, error) {
+	url, file, err := CachedAsset(t)
+	if err != nil {
+		return "", nil, err
+	}
+	data, err := os.ReadFile(file)
+	if err != nil {
+		return "", nil, err
+	}
+
+	name := path.Base(url)
+	ae, ok := NewExtractor(name, t.Name, nil).(*ArchiveExtractor)
+	if !ok {
+		return url, []ArchiveEntry{{Name: name, Mode: "-", Installed: true}}, nil
+	}
+	ar, err := ae.Ar(data, ae.Decompress)
+	if err != nil {
+		return "", nil, err
+	}
+	entries := []ArchiveEntry{}
+	for {
+		f, err := ar.Next()
+		if err == io.EOF {
+			break
+		} else if err != nil {
+			return "", nil, err
+		}
+		entries = append(entries, ArchiveEntry{
+			Name:      f.Name,
+			Mode:      f.Mode.String(),
+			Link:      f.LinkName,
+			Installed: !f.Dir() && sameName(path.Base(f.Name), t.Name),
+		})
+	}
+	return url, entries, nil
+}
+
+// PrintContents writes the files of a release asset to w in the given
+// format: "table" (the default), "json" or "porcelain".
+func PrintContents(w io.Writer, url string, entries []ArchiveEntry, format string) error {
+	switch format {
+	case "", "table":
+		fmt.Fprintf(w, "%s\n\n", url)
+		tw := NewTable(w, 2).Limit(2, maxPathWidth).Middle(2)
+		fmt.Fprintln(tw, "INSTALLED\tMODE\tNAME")
+		for _, e := range entries {
+			installed, name := "", e.Name
+			if e.Installed {
+				installed = "*"
+			}
+			if e.Link != "" {
+				name += " -> " + e.Link
+			}
+			fmt.Fprintf(tw, "%s\t%s\t%s\n", installed, e.Mode, name)
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(struct {
+			URL   string         `json:"url"`
+			Files []ArchiveEntry `json:"files"`
+		}{url, entries})
+	case "porcelain":
+		for _, e := range entries {
+			writePorcelain(w, e.Name, e.Mode, e.Link, fmt.Sprint(e.Installed))
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
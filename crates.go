@@ -0,0 +1,95 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"os"
+	"os/exec"
+	"path"
+	"strings"
+)
+
+// A Crate is a Rust crate on crates.io that is built from a tracked repo.
+type Crate struct {
+	Name       string `json:"name"`
+	Version    string `json:"max_stable_version"`
+	Repository string `json:"repository"`
+}
+
+// getCrate fetches the crate with the given name from crates.io. It returns
+// nil if there is no such crate.
+func getCrate(name string) (*Crate, error) {
+	api := fmt.Sprintf("https://crates.io/api/v1/crates/%s", url.PathEscape(name))
+	resp, err := Get(api)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode == http.StatusNotFound {
+		return nil, nil
+	}
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("crates.io: %s (URL: %s)", resp.Status, api)
+	}
+
+	var result struct {
+		Crate Crate `json:"crate"`
+	}
+	if err := json.Unmarshal(body, &result); err != nil {
+		return nil, err
+	}
+	return &result.Crate, nil
+}
+
+// FindCrate returns the crate built from repo, looked up on crates.io by the
+// name of the repo and of its binary. A crate only matches if its repository
+// is repo, so unrelated crates with the same name are ignored. It returns nil
+// if the repo is not published as a crate.
+func FindCrate(repo, binary string) (*Crate, error) {
+	u := repoURL(repo)
+	if u == "" {
+		return nil, nil
+	}
+	names := []string{strings.ToLower(path.Base(u))}
+	if b := strings.ToLower(strings.TrimSuffix(binary, ".exe")); b != "" && b != names[0] {
+		names = append(names, b)
+	}
+	for _, name := range names {
+		c, err := getCrate(name)
+		if err != nil {
+			return nil, err
+		}
+		if c != nil && strings.EqualFold(strings.TrimSuffix(strings.TrimSuffix(c.Repository, "/"), ".git"), u) {
+			return c, nil
+		}
+	}
+	return nil, nil
+}
+
+// Binstall installs crate with cargo-binstall instead of eget's own download,
+// into dir (cargo's default if empty), at the given version (the latest if
+// empty).
+func Binstall(crate *Crate, dir, version string) error {
+	if _, err := exec.LookPath("cargo-binstall"); err != nil {
+		return fmt.Errorf("cargo-binstall is not installed: %w", err)
+	}
+	args := []string{"binstall", "--no-confirm"}
+	if dir != "" {
+		args = append(args, "--install-path", dir)
+	}
+	name := crate.Name
+	if version != "" {
+		name += "@" + strings.TrimPrefix(version, "v")
+	}
+	cmd := exec.Command("cargo", append(args, name)...)
+	cmd.Stdout = os.Stderr
+	cmd.Stderr = os.Stderr
+	return cmd.Run()
+}
@@ -209,6 +209,7 @@ func (s *SingleAssetDetector) Detect(assets []string) (string, []string, error)
 type SystemDetector struct {
 	Os   OS
 	Arch Arch
+	Libc string // C library to prefer among several matches, such as LibcMusl
 }
 
 // NewSystemDetector returns a new detector for the given OS/Arch as given by
@@ -265,6 +266,11 @@ func (d *SystemDetector) Detect(assets []string) (string, []string, error) {
 	} else if len(matches) == 1 {
 		return matches[0], nil, nil
 	} else if len(matches) > 1 {
+		if libc := d.libcMatches(matches); len(libc) == 1 {
+			return libc[0], nil, nil
+		} else if len(libc) > 1 {
+			matches = libc
+		}
 		return "", matches, fmt.Errorf("%d matches found", len(matches))
 	} else if len(candidates) == 1 {
 		return candidates[0], nil, nil
@@ -275,3 +281,18 @@ func (d *SystemDetector) Detect(assets []string) (string, []string, error) {
 	}
 	return "", all, fmt.Errorf("no candidates found")
 }
+
+// libcMatches returns the assets built for the detector's C library. Only
+// musl is preferred, since glibc builds are usually not marked as such.
+func (d *SystemDetector) libcMatches(assets []string) []string {
+	if d.Libc != LibcMusl {
+		return nil
+	}
+	matched := []string{}
+	for _, a := range assets {
+		if strings.Contains(strings.ToLower(path.Base(a)), "musl") {
+			matched = append(matched, a)
+		}
+	}
+	return matched
+}
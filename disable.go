@@ -0,0 +1,43 @@
+package main
+
+import (
+	"fmt"
+	"os"
+)
+
+// disabledSuffix is appended to the file name of a disabled tool, which
+// takes it off PATH without deleting it.
+const disabledSuffix = ".disabled"
+
+// DisableTool renames the binary of t to its disabled name and logs it as
+// disabled.
+func DisableTool(t Tool) error {
+	if t.Status != StatusInstalled {
+		return fmt.Errorf("`%s` is %s", t.Name, t.Status)
+	}
+	if err := CheckLogWritable(); err != nil {
+		return err
+	}
+	if err := os.Rename(rootPath(t.Path), rootPath(t.Path+disabledSuffix)); err != nil {
+		return err
+	}
+	return LogOperation(t.Repo, t.Path, StatusDisabled, t.Version, t.Verified)
+}
+
+// EnableTool renames the binary of a disabled tool back and logs it as
+// enabled, after which it is installed again.
+func EnableTool(t Tool) error {
+	if t.Status != StatusDisabled {
+		return fmt.Errorf("`%s` is %s, not %s", t.Name, t.Status, StatusDisabled)
+	}
+	if _, err := os.Stat(rootPath(t.Path)); err == nil {
+		return fmt.Errorf("cannot enable `%s`: another file exists at %s", t.Name, t.Path)
+	}
+	if err := CheckLogWritable(); err != nil {
+		return err
+	}
+	if err := os.Rename(rootPath(t.Path+disabledSuffix), rootPath(t.Path)); err != nil {
+		return err
+	}
+	return LogOperation(t.Repo, t.Path, ActionEnabled, t.Version, t.Verified)
+}
@@ -1,14 +1,24 @@
 package main
 
 import (
+	"bytes"
+	"crypto/sha256"
 	"crypto/tls"
+	"crypto/x509"
+	"encoding/hex"
 	"encoding/json"
 	"errors"
 	"fmt"
 	"io"
+	"math/rand"
+	"net"
 	"net/http"
 	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
 	"strings"
+	"sync"
 	"time"
 
 	pb "github.com/schollz/progressbar/v3"
@@ -36,9 +46,47 @@ func getGithubToken() (string, error) {
 	if os.Getenv("GITHUB_TOKEN") != "" {
 		return tokenFrom(os.Getenv("GITHUB_TOKEN"))
 	}
+	if token := ghToken(); token != "" {
+		return token, nil
+	}
 	return "", ErrNoToken
 }
 
+var (
+	ghTokenOnce  sync.Once
+	ghTokenValue string
+)
+
+// ghToken returns the token the GitHub CLI is logged in with, which gh keeps
+// in the OS keyring, or "" if gh is not installed or not logged in. It is
+// only looked up once.
+func ghToken() string {
+	ghTokenOnce.Do(func() {
+		if _, err := exec.LookPath("gh"); err != nil {
+			return
+		}
+		out, err := exec.Command("gh", "auth", "token", "--hostname", "github.com").Output()
+		if err == nil {
+			ghTokenValue = strings.TrimSpace(string(out))
+		}
+	})
+	return ghTokenValue
+}
+
+// Login logs in to GitHub through the GitHub CLI, which uses GitHub's device
+// authorization flow and stores the token in the OS keyring. eget uses that
+// token when no token is set in the environment or config file.
+func Login() error {
+	if _, err := exec.LookPath("gh"); err != nil {
+		return fmt.Errorf("logging in needs the GitHub CLI (https://cli.github.com): %w", err)
+	}
+	cmd := exec.Command("gh", "auth", "login", "--hostname", "github.com", "--git-protocol", "https", "--web")
+	cmd.Stdin = os.Stdin
+	cmd.Stdout = os.Stdout
+	cmd.Stderr = os.Stderr
+	return cmd.Run()
+}
+
 func SetAuthHeader(req *http.Request) *http.Request {
 	token, err := getGithubToken()
 	if err != nil && !errors.Is(err, ErrNoToken) {
@@ -56,21 +104,236 @@ func SetAuthHeader(req *http.Request) *http.Request {
 	return req
 }
 
+// A request refused because of a rate limit is retried after the limit
+// resets, up to maxRateLimitRetries times, if that is at most
+// maxRateLimitWait away.
+const (
+	maxRateLimitWait    = time.Minute
+	maxRateLimitRetries = 3
+)
+
+// A RateLimitError is returned for requests to a host whose API rate limit
+// is exhausted until Reset.
+type RateLimitError struct {
+	Host  string
+	Reset time.Time
+}
+
+func (e *RateLimitError) Error() string {
+	return fmt.Sprintf("%s rate limit exceeded until %s", e.Host, e.Reset.Local().Format("15:04"))
+}
+
+var (
+	rateLimitMu sync.Mutex
+	rateLimited = make(map[string]time.Time)
+)
+
+// rateLimitReset returns when the rate limit that made the server refuse
+// resp resets, or false if resp was not refused because of a rate limit.
+func rateLimitReset(resp *http.Response) (time.Time, bool) {
+	if resp.StatusCode != http.StatusForbidden && resp.StatusCode != http.StatusTooManyRequests {
+		return time.Time{}, false
+	}
+	if s := resp.Header.Get("Retry-After"); s != "" {
+		if secs, err := strconv.Atoi(s); err == nil {
+			return time.Now().Add(time.Duration(secs) * time.Second), true
+		}
+		if t, err := http.ParseTime(s); err == nil {
+			return t, true
+		}
+	}
+	// GitHub sends the X-RateLimit headers, GitLab and Gitea the others
+	for _, prefix := range []string{"X-RateLimit-", "RateLimit-"} {
+		if resp.Header.Get(prefix+"Remaining") != "0" {
+			continue
+		}
+		if reset, err := strconv.ParseInt(resp.Header.Get(prefix+"Reset"), 10, 64); err == nil {
+			return time.Unix(reset, 0), true
+		}
+	}
+	return time.Time{}, false
+}
+
+// A cachedResponse is a JSON API response kept with its ETag, so the same
+// request can be made conditional.
+type cachedResponse struct {
+	ETag string `json:"etag"`
+	Body []byte `json:"body"`
+}
+
+// apiCacheName returns the name of the cache file for responses to url.
+func apiCacheName(url string) string {
+	sum := sha256.Sum256([]byte(url))
+	return filepath.Join("api", hex.EncodeToString(sum[:])+".json")
+}
+
+// Get sends a GET request for url, with the GitHub token if it is for the
+// GitHub API. A request refused because of a rate limit that resets soon is
+// retried once it does, with some jitter so concurrent requests do not all
+// retry at once. Otherwise it fails with a *RateLimitError, and so do later
+// requests to the same host until the limit resets, without being sent.
+//
+// JSON responses with an ETag, such as release metadata and crates.io
+// lookups, are cached, and repeated requests send it in If-None-Match. When the server answers that
+// nothing changed, which does not count against GitHub's rate limit, the
+// cached response is returned as if it had been sent again.
 func Get(url string) (*http.Response, error) {
-	req, err := http.NewRequest("GET", url, nil)
+	var cached cachedResponse
+	readCache(apiCacheName(url), &cached)
+
+	for attempt := 0; ; attempt++ {
+		req, err := http.NewRequest("GET", url, nil)
+
+		if err != nil {
+			return nil, err
+		}
+
+		host := req.URL.Host
+		rateLimitMu.Lock()
+		reset := rateLimited[host]
+		rateLimitMu.Unlock()
+		if time.Now().Before(reset) {
+			return nil, &RateLimitError{Host: host, Reset: reset}
+		}
+
+		req = SetAuthHeader(req)
+		// crates.io rejects requests that do not identify the client
+		req.Header.Set("User-Agent", fmt.Sprintf("eget/%s (https://github.com/%s)", Version, selfRepo))
+		if cached.ETag != "" {
+			req.Header.Set("If-None-Match", cached.ETag)
+		}
+
+		resp, err := httpClient().Do(req)
+		if err != nil {
+			return nil, err
+		}
+		reset, limited := rateLimitReset(resp)
+		if !limited {
+			return cacheResponse(url, resp, cached)
+		}
+		resp.Body.Close()
+
+		wait := time.Until(reset)
+		if wait > maxRateLimitWait || attempt == maxRateLimitRetries {
+			rateLimitMu.Lock()
+			rateLimited[host] = reset
+			rateLimitMu.Unlock()
+			return nil, &RateLimitError{Host: host, Reset: reset}
+		}
+		if wait < 0 {
+			wait = 0
+		}
+		time.Sleep(wait + time.Duration(rand.Int63n(int64(time.Second)<<attempt)))
+	}
+}
+
+// cacheResponse returns the cached response for a 304 Not Modified resp, and
+// caches resp if it is a JSON response with an ETag.
+func cacheResponse(url string, resp *http.Response, cached cachedResponse) (*http.Response, error) {
+	switch {
+	case resp.StatusCode == http.StatusNotModified && cached.ETag != "":
+		resp.Body.Close()
+		resp.StatusCode, resp.Status = http.StatusOK, "200 OK"
+		resp.Body = io.NopCloser(bytes.NewReader(cached.Body))
+		resp.ContentLength = int64(len(cached.Body))
+	case resp.StatusCode == http.StatusOK && resp.Header.Get("ETag") != "" &&
+		strings.HasPrefix(resp.Header.Get("Content-Type"), "application/json"):
+		body, err := io.ReadAll(resp.Body)
+		resp.Body.Close()
+		if err != nil {
+			return nil, err
+		}
+		writeCache(apiCacheName(url), cachedResponse{ETag: resp.Header.Get("ETag"), Body: body})
+		resp.Body = io.NopCloser(bytes.NewReader(body))
+	}
+	return resp, nil
+}
 
+// Head sends a HEAD request for url, with the same proxy and SSL settings as
+// Get.
+func Head(url string) (*http.Response, error) {
+	req, err := http.NewRequest("HEAD", url, nil)
 	if err != nil {
 		return nil, err
 	}
 
-	req = SetAuthHeader(req)
+	return httpClient().Do(req)
+}
 
-	proxyClient := &http.Client{Transport: &http.Transport{
-		Proxy:           http.ProxyFromEnvironment,
-		TLSClientConfig: &tls.Config{InsecureSkipVerify: opts.DisableSSL},
+// Post sends a POST request with the given body to url, with the same proxy
+// and SSL settings as Get.
+func Post(url, contentType string, body io.Reader) (*http.Response, error) {
+	req, err := http.NewRequest("POST", url, body)
+	if err != nil {
+		return nil, err
+	}
+	req.Header.Set("Content-Type", contentType)
+
+	return httpClient().Do(req)
+}
+
+// Timeouts for connecting to a server and for it to start responding. The
+// body of a response, such as a large download, may take longer.
+const (
+	connectTimeout  = 30 * time.Second
+	responseTimeout = 60 * time.Second
+)
+
+// httpClient returns a client that honors the proxy environment variables
+// (HTTP_PROXY, HTTPS_PROXY and NO_PROXY), the extra root certificates of
+// ca_file and --disable-ssl. A server that does not respond in time fails the
+// request rather than hanging, such as one of many concurrent update checks.
+func httpClient() *http.Client {
+	if opts.Offline {
+		return &http.Client{Transport: offlineTransport{}}
+	}
+	return &http.Client{Transport: &http.Transport{
+		Proxy:                 http.ProxyFromEnvironment,
+		DialContext:           (&net.Dialer{Timeout: connectTimeout}).DialContext,
+		TLSHandshakeTimeout:   connectTimeout,
+		ResponseHeaderTimeout: responseTimeout,
+		TLSClientConfig: &tls.Config{
+			InsecureSkipVerify: opts.DisableSSL,
+			RootCAs:            rootCAs,
+		},
 	}}
+}
+
+// ErrOffline is returned for every request made with --offline.
+var ErrOffline = errors.New("network access disabled by --offline")
+
+// offlineTransport fails every request without touching the network.
+type offlineTransport struct{}
 
-	return proxyClient.Do(req)
+func (offlineTransport) RoundTrip(req *http.Request) (*http.Response, error) {
+	return nil, ErrOffline
+}
+
+// rootCAs is the certificate pool used to verify servers, or nil to use the
+// system's. It is set by LoadRootCAs.
+var rootCAs *x509.CertPool
+
+// LoadRootCAs adds the PEM encoded certificates in file to the system's root
+// certificates for every request, for networks that intercept TLS with their
+// own certificate authority.
+func LoadRootCAs(file string) error {
+	path, err := home.Expand(file)
+	if err != nil {
+		return err
+	}
+	pem, err := os.ReadFile(path)
+	if err != nil {
+		return fmt.Errorf("ca_file: %w", err)
+	}
+	pool, err := x509.SystemCertPool()
+	if err != nil || pool == nil {
+		pool = x509.NewCertPool()
+	}
+	if !pool.AppendCertsFromPEM(pem) {
+		return fmt.Errorf("ca_file: no certificates found in %s", path)
+	}
+	rootCAs = pool
+	return nil
 }
 
 type RateLimitJson struct {
@@ -111,7 +374,7 @@ func GetRateLimit() (RateLimit, error) {
 
 	req.Header.Set("Accept", "application/vnd.github.v3+json")
 
-	resp, err := http.DefaultClient.Do(req)
+	resp, err := httpClient().Do(req)
 	if err != nil {
 		return RateLimit{}, err
 	}
@@ -129,10 +392,199 @@ func GetRateLimit() (RateLimit, error) {
 	return parsed.Resources["core"], err
 }
 
+// maxResumes is how many times a download that fails midway is resumed
+// before giving up.
+const maxResumes = 3
+
+// downloadTTL is how long a downloaded asset is kept in the download cache
+// after it was last used.
+const downloadTTL = 30 * 24 * time.Hour
+
+// A cachedDownload describes a file in the download cache, complete or not.
+type cachedDownload struct {
+	URL       string `json:"url"`
+	Validator string `json:"validator"`        // ETag or Last-Modified of the response the file came from
+	SHA256    string `json:"sha256,omitempty"` // checksum of the complete file
+}
+
+// downloadCacheDir returns the directory assets are downloaded to: the
+// download_cache setting, or the downloads directory of the cache.
+func downloadCacheDir() (string, error) {
+	if opts.DLCache != "" {
+		return opts.DLCache, nil
+	}
+	dir, err := GetCacheDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(dir, "downloads"), nil
+}
+
+// readDownloadMeta reads the description of the cached download of url from
+// path. It returns a blank description for url if there is none.
+func readDownloadMeta(path, url string) cachedDownload {
+	var meta cachedDownload
+	if data, err := os.ReadFile(path); err == nil {
+		json.Unmarshal(data, &meta)
+	}
+	if meta.URL != url {
+		return cachedDownload{URL: url}
+	}
+	return meta
+}
+
+// writeDownloadMeta writes the description of a cached download to path. It
+// is written to a temporary file that replaces path, so a concurrent
+// download never reads a partly written description.
+func writeDownloadMeta(path string, meta cachedDownload) error {
+	data, err := json.Marshal(meta)
+	if err != nil {
+		return err
+	}
+	f, err := os.CreateTemp(filepath.Dir(path), filepath.Base(path)+".*.tmp")
+	if err != nil {
+		return err
+	}
+	_, err = f.Write(data)
+	if cerr := f.Close(); err == nil {
+		err = cerr
+	}
+	if err == nil {
+		err = os.Rename(f.Name(), path)
+	}
+	if err != nil {
+		os.Remove(f.Name())
+	}
+	return err
+}
+
+// staleLock is how long the lock on a partial download is honoured after it
+// or the partial file was last written, in case the download holding it
+// crashed.
+const staleLock = 10 * time.Minute
+
+// openPartial opens the file the download to file is written to. The shared
+// partial file, file.part, which a later run can resume, is locked by
+// creating file.lock exclusively, so concurrent downloads of the same URL,
+// with max_downloads or by other processes, never write to it at the same
+// time. While another download holds the lock, a private temporary file is
+// used instead. shared reports which of the two f is. The returned release
+// function removes the lock or the private file; it must be called once f is
+// closed.
+func openPartial(file string) (f *os.File, shared bool, release func(), err error) {
+	part, lock := file+".part", file+".lock"
+	for i := 0; i < 2; i++ {
+		l, err := os.OpenFile(lock, os.O_WRONLY|os.O_CREATE|os.O_EXCL, 0644)
+		if err == nil {
+			l.Close()
+			f, err := os.OpenFile(part, os.O_RDWR|os.O_CREATE, 0644)
+			if err != nil {
+				os.Remove(lock)
+				return nil, false, nil, err
+			}
+			return f, true, func() { os.Remove(lock) }, nil
+		} else if !os.IsExist(err) {
+			return nil, false, nil, err
+		}
+		if !lockIsStale(lock, part) {
+			break
+		}
+		os.Remove(lock)
+	}
+	f, err = os.CreateTemp(filepath.Dir(file), filepath.Base(file)+".*.part")
+	if err != nil {
+		return nil, false, nil, err
+	}
+	name := f.Name()
+	return f, false, func() { os.Remove(name) }, nil
+}
+
+// lockIsStale returns true if neither lock nor the partial file part was
+// written to for staleLock.
+func lockIsStale(lock, part string) bool {
+	latest := time.Time{}
+	for _, p := range []string{lock, part} {
+		if fi, err := os.Stat(p); err == nil && fi.ModTime().After(latest) {
+			latest = fi.ModTime()
+		}
+	}
+	return time.Since(latest) > staleLock
+}
+
+// cachedFile returns the file the download of url was cached in, if it is
+// still intact and, unless --offline is given, the server reports that it
+// has not changed. The file is reused instead of downloading it again. The
+// server is asked with a conditional HEAD request, and servers that ignore
+// the condition are compared by the ETag or Last-Modified they report.
+func cachedFile(file string, meta cachedDownload) (*os.File, bool) {
+	if meta.SHA256 == "" {
+		return nil, false
+	}
+	if sum, err := fileSha256(file); err != nil || sum != meta.SHA256 {
+		return nil, false
+	}
+	if !opts.Offline {
+		req, err := http.NewRequest("HEAD", meta.URL, nil)
+		if err != nil {
+			return nil, false
+		}
+		req = SetAuthHeader(req)
+		if strings.HasPrefix(meta.Validator, `"`) || strings.HasPrefix(meta.Validator, `W/"`) {
+			req.Header.Set("If-None-Match", meta.Validator)
+		} else if meta.Validator != "" {
+			req.Header.Set("If-Modified-Since", meta.Validator)
+		}
+		resp, err := httpClient().Do(req)
+		if err != nil {
+			return nil, false
+		}
+		resp.Body.Close()
+		switch {
+		case resp.StatusCode == http.StatusNotModified:
+		case resp.StatusCode == http.StatusOK && meta.Validator != "" &&
+			(resp.Header.Get("ETag") == meta.Validator || resp.Header.Get("Last-Modified") == meta.Validator):
+		default:
+			return nil, false
+		}
+	}
+	f, err := os.Open(file)
+	if err != nil {
+		return nil, false
+	}
+	now := time.Now()
+	os.Chtimes(file, now, now)
+	os.Chtimes(file+".json", now, now)
+	return f, true
+}
+
+// pruneDownloads removes the files in the download cache dir that were not
+// used for downloadTTL.
+func pruneDownloads(dir string) {
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		return
+	}
+	for _, e := range entries {
+		if fi, err := e.Info(); err == nil && fi.Mode().IsRegular() && time.Since(fi.ModTime()) > downloadTTL {
+			os.Remove(filepath.Join(dir, e.Name()))
+		}
+	}
+}
+
 // Download the file at 'url' and write the http response body to 'out'. The
 // 'getbar' function allows the caller to construct a progress bar given the
 // size of the file being downloaded, and the download will write to the
 // returned progress bar.
+//
+// Files are downloaded to the download cache, named by the checksum of their
+// URL. A file that is already there is reused if it is intact and the server
+// reports that it has not changed, so installing the same release again, or
+// to another --root, does not download it again. If the connection fails
+// midway, the download is resumed with a range request, up to maxResumes
+// times; a partial file left by a failed run is resumed by the next one, as
+// long as the server still has the same file. Concurrent downloads of the
+// same URL each write their own file, see openPartial, and the complete file
+// is renamed into place.
 func Download(url string, out io.Writer, getbar func(size int64) *pb.ProgressBar) error {
 	if IsLocalFile(url) {
 		f, err := os.Open(url)
@@ -144,21 +596,154 @@ func Download(url string, out io.Writer, getbar func(size int64) *pb.ProgressBar
 		return err
 	}
 
-	resp, err := Get(url)
+	dir, err := downloadCacheDir()
 	if err != nil {
 		return err
 	}
-	defer resp.Body.Close()
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return err
+	}
+	sum := sha256.Sum256([]byte(url))
+	file := filepath.Join(dir, hex.EncodeToString(sum[:]))
+	metaFile := file + ".json"
+	meta := readDownloadMeta(metaFile, url)
 
-	if resp.StatusCode != http.StatusOK {
-		body, err := io.ReadAll(resp.Body)
+	if f, ok := cachedFile(file, meta); ok {
+		defer f.Close()
+		fmt.Fprintf(os.Stderr, "Using the cached download of %s\n", url)
+		_, err := io.Copy(out, f)
+		return err
+	}
+
+	f, shared, release, err := openPartial(file)
+	if err != nil {
+		return err
+	}
+	unregister := onInterrupt(release)
+	defer func() {
+		f.Close()
+		release()
+		unregister()
+	}()
+	if !shared {
+		// the description of the shared partial file belongs to the download
+		// holding its lock; this one starts over
+		meta = cachedDownload{URL: url}
+	}
+
+	for attempt := 0; ; attempt++ {
+		offset, err := f.Seek(0, io.SeekEnd)
 		if err != nil {
 			return err
 		}
-		return fmt.Errorf("download error: %d: %s", resp.StatusCode, body)
+		req, err := http.NewRequest("GET", url, nil)
+		if err != nil {
+			return err
+		}
+		req = SetAuthHeader(req)
+		if offset > 0 && meta.Validator != "" && meta.SHA256 == "" {
+			req.Header.Set("Range", fmt.Sprintf("bytes=%d-", offset))
+			req.Header.Set("If-Range", meta.Validator)
+		}
+
+		resp, err := httpClient().Do(req)
+		if err != nil {
+			return err
+		}
+		switch resp.StatusCode {
+		case http.StatusPartialContent:
+		case http.StatusOK:
+			// the server sent the whole file, because it changed or does
+			// not support ranges
+			if err := f.Truncate(0); err != nil {
+				resp.Body.Close()
+				return err
+			}
+			if offset, err = f.Seek(0, io.SeekStart); err != nil {
+				resp.Body.Close()
+				return err
+			}
+		default:
+			body, err := io.ReadAll(resp.Body)
+			resp.Body.Close()
+			if err != nil {
+				return err
+			}
+			return fmt.Errorf("download error: %d: %s", resp.StatusCode, body)
+		}
+
+		meta.SHA256 = ""
+		meta.Validator = resp.Header.Get("ETag")
+		if meta.Validator == "" {
+			meta.Validator = resp.Header.Get("Last-Modified")
+		}
+		if shared {
+			writeDownloadMeta(metaFile, meta)
+		}
+
+		size := resp.ContentLength
+		if size >= 0 {
+			size += offset
+		}
+		bar := getbar(size)
+		bar.Add64(offset)
+		_, err = io.Copy(io.MultiWriter(f, bar), limitReader(resp.Body))
+		resp.Body.Close()
+		if err == nil {
+			break
+		}
+		if meta.Validator == "" {
+			return err
+		} else if attempt == maxResumes && !shared {
+			return err
+		} else if attempt == maxResumes {
+			return fmt.Errorf("%w (the partial download is resumed by the next attempt)", err)
+		}
+		fmt.Fprintf(os.Stderr, "\nconnection lost, resuming download: %v\n", err)
 	}
 
-	bar := getbar(resp.ContentLength)
-	_, err = io.Copy(io.MultiWriter(out, bar), resp.Body)
-	return err
+	if _, err := f.Seek(0, io.SeekStart); err != nil {
+		return err
+	}
+	h := sha256.New()
+	if _, err := io.Copy(io.MultiWriter(out, h), f); err != nil {
+		return err
+	}
+	f.Close()
+
+	// only files the server can validate can be reused
+	if meta.Validator == "" {
+		if shared {
+			os.Remove(f.Name())
+			os.Remove(metaFile)
+		}
+		return nil
+	}
+	if err := os.Rename(f.Name(), file); err == nil {
+		meta.SHA256 = hex.EncodeToString(h.Sum(nil))
+		unverified[url] = meta
+	}
+	pruneDownloads(dir)
+	return nil
+}
+
+// unverified holds the descriptions of the files downloaded by this run
+// that were not verified yet, by URL. They are written by downloadVerified.
+var unverified = make(map[string]cachedDownload)
+
+// downloadVerified records that the download of url passed verification, so
+// later runs can reuse the cached file. Until then its checksum is not in
+// the cache, and a download that turns out to be corrupted is never reused.
+func downloadVerified(url string) error {
+	meta, ok := unverified[url]
+	if !ok {
+		return nil
+	}
+	delete(unverified, url)
+	dir, err := downloadCacheDir()
+	if err != nil {
+		return err
+	}
+	sum := sha256.Sum256([]byte(url))
+	return writeDownloadMeta(filepath.Join(dir, hex.EncodeToString(sum[:])+".json"), meta)
 }
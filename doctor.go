@@ -0,0 +1,301 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"sort"
+	"strings"
+)
+
+// A Check is the result of a single diagnostic performed by --doctor. Failed
+// checks carry a suggested fix.
+type Check struct {
+	OK      bool
+	Message string
+	Fix     string
+}
+
+func pass(format string, a ...interface{}) Check {
+	return Check{OK: true, Message: fmt.Sprintf(format, a...)}
+}
+
+func fail(fix string, format string, a ...interface{}) Check {
+	return Check{Message: fmt.Sprintf(format, a...), Fix: fix}
+}
+
+// Doctor diagnoses the log, the install directory and the tracked binaries.
+func Doctor() []Check {
+	checks := []Check{}
+	checks = append(checks, checkLog()...)
+	checks = append(checks, checkInstallDir()...)
+	// the executables on PATH belong to this system, not the --root
+	if opts.Root == "" {
+		checks = append(checks, checkEget())
+	}
+	checks = append(checks, checkTools()...)
+	if opts.Root == "" {
+		checks = append(checks, checkPackageManagers()...)
+		checks = append(checks, checkCompatibility()...)
+	}
+	return checks
+}
+
+// PrintChecks writes the checks to w and returns the number of failed checks.
+func PrintChecks(w io.Writer, checks []Check) int {
+	failed := 0
+	for _, c := range checks {
+		if c.OK {
+			fmt.Fprintf(w, "ok: %s\n", c.Message)
+			continue
+		}
+		failed++
+		fmt.Fprintf(w, "problem: %s\n  fix: %s\n", c.Message, c.Fix)
+	}
+	return failed
+}
+
+// onPath returns true if dir is one of the directories in $PATH.
+func onPath(dir string) bool {
+	abs, err := filepath.Abs(dir)
+	if err != nil {
+		return false
+	}
+	for _, p := range filepath.SplitList(os.Getenv("PATH")) {
+		if p == "" {
+			continue
+		}
+		if pabs, err := filepath.Abs(p); err == nil && pabs == abs {
+			return true
+		}
+	}
+	return false
+}
+
+// dirWritable returns nil if a file can be created in dir.
+func dirWritable(dir string) error {
+	f, err := os.CreateTemp(dir, ".eget-doctor-")
+	if err != nil {
+		return err
+	}
+	f.Close()
+	return os.Remove(f.Name())
+}
+
+func checkLog() []Check {
+	logFile, err := GetLogFilePath()
+	if err != nil {
+		return []Check{fail("make sure your home directory can be determined", "cannot locate the log: %v", err)}
+	}
+
+	data, err := os.ReadFile(logFile)
+	if os.IsNotExist(err) {
+		return []Check{fail("install a tool with eget to create it", "log %s does not exist", logFile)}
+	} else if err != nil {
+		return []Check{fail(fmt.Sprintf("make %s readable by your user", logFile), "cannot read log: %v", err)}
+	}
+
+	entries, malformed := parseLogs(data)
+	checks := []Check{pass("log %s has %d entries", logFile, len(entries))}
+	if malformed > 0 {
+		checks = append(checks, fail("run `eget --prune` to rewrite the log without them",
+			"log has %d malformed lines", malformed))
+	}
+
+	f, err := os.OpenFile(logFile, os.O_WRONLY|os.O_APPEND, 0)
+	if err != nil {
+		checks = append(checks, fail(fmt.Sprintf("make %s writable by your user", logFile),
+			"log is not writable: %v", err))
+	} else {
+		f.Close()
+	}
+	return checks
+}
+
+func checkInstallDir() []Check {
+	dir := installDir()
+	if dir == "" {
+		return []Check{fail("set $EGET_BIN (or `target` in the config file) to a directory on your PATH",
+			"no install directory configured, binaries are extracted to the current directory")}
+	}
+	if !IsDirectory(rootPath(dir)) {
+		return []Check{fail(fmt.Sprintf("create it with `mkdir -p %s`", dir), "install directory %s does not exist", dir)}
+	}
+
+	checks := []Check{pass("install directory %s exists", dir)}
+	if opts.Root != "" {
+		// PATH of this system says nothing about the other root
+	} else if onPath(dir) {
+		checks = append(checks, pass("install directory is on PATH"))
+	} else {
+		checks = append(checks, fail(pathFix(dir), "install directory %s is not on PATH", dir))
+	}
+	if err := dirWritable(rootPath(dir)); err != nil {
+		checks = append(checks, fail(fmt.Sprintf("make %s writable by your user", dir),
+			"install directory is not writable: %v", err))
+	}
+	return checks
+}
+
+// checkEget checks that eget can be run by name. If it cannot, the fix
+// bootstraps a copy into the install directory with the running binary.
+func checkEget() Check {
+	p, err := exec.LookPath("eget")
+	if err != nil {
+		fix := "move the eget binary to a directory on your PATH"
+		if exe, err := os.Executable(); err == nil && installDir() != "" && onPath(installDir()) {
+			fix = fmt.Sprintf("install it with `%s %s --to %s`", exe, selfRepo, installDir())
+		}
+		return fail(fix, "eget is not on PATH")
+	}
+	return pass("eget found at %s", p)
+}
+
+func checkTools() []Check {
+	tools, err := LoadTools()
+	if err != nil {
+		return []Check{fail("run `eget --log` to inspect the log", "cannot load tracked binaries: %v", err)}
+	}
+
+	checks := []Check{}
+	owners := make(map[string][]string)
+	seen := make(map[string]bool)
+	dirs := []string{}
+	for _, t := range tools {
+		switch t.Status {
+		case StatusMissing:
+			checks = append(checks, fail(fmt.Sprintf("reinstall it with `eget %s` or run `eget --prune --missing`", t.Repo),
+				"%s is missing from %s", t.Name, t.Path))
+		case StatusBroken:
+			checks = append(checks, fail(fmt.Sprintf("reinstall it with `eget %s` or run `eget --prune --missing`", t.Repo),
+				"%s at %s is a symlink to %s, which does not exist", t.Name, t.Path, t.Target))
+		case StatusInstalled:
+			owners[t.Name] = append(owners[t.Name], t.Path)
+			if dir := filepath.Dir(t.Path); !seen[dir] {
+				seen[dir] = true
+				dirs = append(dirs, dir)
+			}
+		}
+	}
+
+	names := make([]string, 0, len(owners))
+	for name := range owners {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		if paths := owners[name]; len(paths) > 1 {
+			checks = append(checks, fail("remove all but one copy with `eget --remove`",
+				"%s is installed %d times: %s", name, len(paths), strings.Join(paths, ", ")))
+		}
+	}
+
+	reported := make(map[string]bool)
+	for _, t := range tools {
+		if len(t.Conflicts) == 0 || t.Status == StatusRemoved || reported[nameKey(t.Name)] {
+			continue
+		}
+		reported[nameKey(t.Name)] = true
+		owner := "none of them is first in PATH"
+		if repo := commandOwner(tools, t.Name); repo != "" && opts.Root == "" {
+			owner = repo + " owns the command"
+		}
+		checks = append(checks, fail("keep one with `eget --remove`, or install the other under another name with --to",
+			"%s is installed by %s; %s", t.Name, strings.Join(append([]string{t.Repo}, t.Conflicts...), " and "), owner))
+	}
+
+	for _, dir := range dirs {
+		if err := dirWritable(rootPath(dir)); err != nil {
+			checks = append(checks, fail(fmt.Sprintf("make %s writable by your user", dir),
+				"binaries in %s cannot be updated or removed: %v", dir, err))
+		}
+	}
+
+	if len(checks) == 0 {
+		checks = append(checks, pass("all %d tracked binaries are healthy", len(tools)))
+	}
+	return checks
+}
+
+// checkPackageManagers looks for tracked binaries that are shadowed by
+// another executable earlier in PATH, or also installed by a system package
+// manager or a version manager such as mise, since all of these lead to
+// confusing version mismatches.
+func checkPackageManagers() []Check {
+	tools, err := LoadTools()
+	if err != nil {
+		return nil
+	}
+
+	checks := []Check{}
+	for _, t := range tools {
+		if t.Status != StatusInstalled {
+			continue
+		}
+		if p := shadowedBy(t); p != "" {
+			checks = append(checks, fail(fmt.Sprintf("remove %s or move %s before %s in PATH", p, filepath.Dir(t.Path), filepath.Dir(p)),
+				"%s at %s is shadowed by %s, which comes earlier in PATH", t.Name, t.Path, p))
+		}
+		for _, p := range lookPathAll(t.Name) {
+			if sameFile(p, t.Path) {
+				continue
+			}
+			if manager := packageOwner(p); manager != "" {
+				checks = append(checks, fail(fmt.Sprintf("uninstall the %s package or remove the eget copy with `eget --remove %s`", manager, t.Path),
+					"%s is also installed by %s at %s", t.Name, manager, p))
+			}
+		}
+		if manager, shim := versionManager(t.Name); manager != "" {
+			checks = append(checks, fail(fmt.Sprintf("uninstall it from %s or remove the eget copy with `eget --remove %s`; set `skip_version_managed = true` to leave its updates to %s", manager, t.Path, manager),
+				"%s is also managed by %s through the shim %s", t.Name, manager, shim))
+		}
+	}
+	if len(checks) == 0 {
+		checks = append(checks, pass("no tracked binary is shadowed or also installed by a package or version manager"))
+	}
+	return checks
+}
+
+// checkCompatibility looks for tracked binaries that cannot run on this
+// system, such as builds for another architecture, glibc builds on a musl
+// system or binaries linked against shared libraries that are not installed.
+func checkCompatibility() []Check {
+	tools, err := LoadTools()
+	if err != nil {
+		return nil
+	}
+
+	problems := make([][]string, len(tools))
+	parallel(len(tools), func(i int) {
+		if tools[i].Status == StatusInstalled {
+			problems[i] = binaryProblems(tools[i].Path)
+		}
+	})
+
+	checks := []Check{}
+	for i, t := range tools {
+		for _, p := range problems[i] {
+			checks = append(checks, fail(fmt.Sprintf("reinstall the build for this system with `eget %s` (add `--asset musl` for a statically linked one), or install missing libraries with your package manager", t.Repo),
+				"%s cannot run here: %s", t.Name, p))
+		}
+	}
+	if len(checks) == 0 {
+		checks = append(checks, pass("all tracked binaries are built for this system"))
+	}
+	return checks
+}
+
+// sameFile returns true if a and b refer to the same file.
+func sameFile(a, b string) bool {
+	fa, err := os.Stat(a)
+	if err != nil {
+		return false
+	}
+	fb, err := os.Stat(b)
+	if err != nil {
+		return false
+	}
+	return os.SameFile(fa, fb)
+}
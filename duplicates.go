@@ -0,0 +1,117 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"sort"
+	"strconv"
+)
+
+// A Duplicate is a set of tracked binaries with identical contents, such as
+// the same tool installed twice to different directories.
+type Duplicate struct {
+	SHA256 string `json:"sha256"`
+	Size   int64  `json:"size"`   // size of one copy
+	Wasted int64  `json:"wasted"` // size of every copy but one
+	Tools  []Tool `json:"tools"`
+}
+
+// FindDuplicates hashes the installed tools and returns the sets of tools
+// with identical contents, the largest waste of space first. Paths that are
+// links to the same file are not copies and are counted once.
+func FindDuplicates(tools []Tool) []Duplicate {
+	paths := make([]string, len(tools))
+	for i, t := range tools {
+		if t.Status == StatusInstalled {
+			paths[i] = rootPath(t.Path)
+		}
+	}
+	sums, _ := fileHashes(paths)
+
+	byHash := make(map[string][]Tool)
+	files := make(map[string][]os.FileInfo)
+outer:
+	for i, t := range tools {
+		sum := sums[i]
+		if sum == "" {
+			continue
+		}
+		fi, err := os.Stat(paths[i])
+		if err != nil {
+			continue
+		}
+		for _, other := range files[sum] {
+			if os.SameFile(fi, other) {
+				continue outer
+			}
+		}
+		files[sum] = append(files[sum], fi)
+		t.Size = fi.Size()
+		byHash[sum] = append(byHash[sum], t)
+	}
+
+	dups := []Duplicate{}
+	for sum, ts := range byHash {
+		if len(ts) < 2 {
+			continue
+		}
+		size := ts[0].Size
+		dups = append(dups, Duplicate{
+			SHA256: sum,
+			Size:   size,
+			Wasted: size * int64(len(ts)-1),
+			Tools:  ts,
+		})
+	}
+	sort.Slice(dups, func(i, j int) bool {
+		if dups[i].Wasted != dups[j].Wasted {
+			return dups[i].Wasted > dups[j].Wasted
+		}
+		return dups[i].SHA256 < dups[j].SHA256
+	})
+	return dups
+}
+
+// PrintDuplicates writes dups to w in the given format: "table" (the
+// default), "json" or "porcelain", followed by the space they waste in
+// total.
+func PrintDuplicates(w io.Writer, dups []Duplicate, format string) error {
+	var wasted int64
+	for _, d := range dups {
+		wasted += d.Wasted
+	}
+
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2).Limit(1, maxNameWidth).Limit(2, maxRepoWidth).Limit(3, maxPathWidth).Middle(3)
+		fmt.Fprintln(tw, "SHA256\tNAME\tREPO\tPATH\tSIZE")
+		for _, d := range dups {
+			for _, t := range d.Tools {
+				fmt.Fprintf(tw, "%s\t%s\t%s\t%s\t%s\n", d.SHA256[:12], t.Name, displayRepo(t), t.Path, formatSize(t.Size))
+			}
+		}
+		if err := tw.Flush(); err != nil {
+			return err
+		}
+		_, err := fmt.Fprintf(w, "\n%d sets of duplicates waste %s\n", len(dups), formatSize(wasted))
+		return err
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(struct {
+			Duplicates []Duplicate `json:"duplicates"`
+			Wasted     int64       `json:"wasted"`
+		}{dups, wasted})
+	case "porcelain":
+		for _, d := range dups {
+			for _, t := range d.Tools {
+				writePorcelain(w, "duplicate", d.SHA256, t.Name, t.Repo, t.Path, strconv.FormatInt(t.Size, 10))
+			}
+		}
+		writePorcelain(w, "wasted", strconv.FormatInt(wasted, 10))
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
@@ -13,16 +13,60 @@ import (
 	"path/filepath"
 	"regexp"
 	"runtime"
+	"sort"
+	"strconv"
 	"strings"
+	"sync"
 	"time"
 
 	"github.com/jessevdk/go-flags"
 	pb "github.com/schollz/progressbar/v3"
 )
 
+// selfRepo is the repository eget's own releases are published from.
+const selfRepo = "zyedidia/eget"
+
+// Exit codes. Every failure that does not have a more specific code exits
+// with ExitError.
+const (
+	ExitOK       = 0 // success
+	ExitError    = 1 // generic error
+	ExitNoMatch  = 2 // the requested tool or search had no match
+	ExitUpdates  = 3 // updates are available
+	ExitProblems = 4 // --doctor found problems or --audit found advisories
+	ExitAborted  = 5 // the user declined a confirmation prompt or interrupted eget
+)
+
 func fatal(a ...interface{}) {
+	for _, v := range a {
+		if err, ok := v.(error); ok && errors.Is(err, ErrCancelled) {
+			exit(ExitAborted, a...)
+		}
+	}
+	exit(ExitError, a...)
+}
+
+// exit prints a message to stderr and exits with the given code. Errors for
+// which a fix is known are followed by the suggested fix.
+func exit(code int, a ...interface{}) {
 	fmt.Fprintln(os.Stderr, a...)
-	os.Exit(1)
+	for _, v := range a {
+		if err, ok := v.(error); ok {
+			if fix := fixFor(err); fix != "" {
+				fmt.Fprintf(os.Stderr, "  fix: %s\n", fix)
+			}
+		}
+	}
+	os.Exit(code)
+}
+
+// warnLog warns that what could not be logged, followed by the suggested fix.
+// The operation itself succeeded, but the log no longer tracks it.
+func warnLog(what string, err error) {
+	fmt.Fprintf(os.Stderr, "Warning: failed to log %s: %v\n", what, err)
+	if fix := fixFor(err); fix != "" {
+		fmt.Fprintf(os.Stderr, "  fix: %s\n", fix)
+	}
 }
 
 // IsUrl returns true if s is a valid URL.
@@ -46,6 +90,50 @@ func IsGithubUrl(s string) bool {
 	return ghrgx.MatchString(s)
 }
 
+var glrgx = regexp.MustCompile(`^(http(s)?://)?gitlab\.com(/[\w\-.]+){2,}?(\.git)?(/)?$`)
+
+// IsGitlabUrl returns true if s is a URL of a project hosted on gitlab.com.
+func IsGitlabUrl(s string) bool {
+	return glrgx.MatchString(s) && !strings.Contains(s, "/-/")
+}
+
+// gitlabProject returns the host and the full project path of a GitLab URL.
+func gitlabProject(s string) (host, project string) {
+	_, after, _ := Cut(s, "gitlab.com/")
+	project = strings.TrimSuffix(strings.Trim(after, "/"), ".git")
+	return "gitlab.com", project
+}
+
+// giteaProject returns the base URL of the instance and the owner/repo of s
+// if s is the URL of a repository on one of the given Gitea hosts.
+func giteaProject(s string, hosts []string) (base, repo string, ok bool) {
+	trimmed := s
+	if _, after, found := Cut(s, "://"); found {
+		trimmed = after
+	}
+	for _, h := range hosts {
+		base = strings.TrimSuffix(h, "/")
+		if !strings.Contains(base, "://") {
+			base = "https://" + base
+		}
+		u, err := url.Parse(base)
+		if err != nil || u.Host == "" {
+			continue
+		}
+		prefix := u.Host + u.Path + "/"
+		if !strings.HasPrefix(trimmed, prefix) {
+			continue
+		}
+		repo = strings.TrimSuffix(strings.Trim(trimmed[len(prefix):], "/"), ".git")
+		parts := strings.Split(repo, "/")
+		if len(parts) != 2 || parts[0] == "" || parts[1] == "" {
+			return "", "", false
+		}
+		return base, repo, true
+	}
+	return "", "", false
+}
+
 func IsLocalFile(s string) bool {
 	_, err := os.Stat(s)
 	return err == nil
@@ -71,12 +159,63 @@ func checksumAsset(asset string, assets []string) string {
 	return ""
 }
 
+// searches for a checksum file covering every asset of the release, such as
+// SHA256SUMS, checksums.txt or tool_1.0.0_checksums.txt
+func checksumsAsset(assets []string) string {
+	for _, a := range assets {
+		name := strings.ToLower(path.Base(a))
+		if name == "sha256sums" || name == "sha256sums.txt" || strings.HasSuffix(name, "checksums.txt") {
+			return a
+		}
+	}
+	return ""
+}
+
 // Determine the appropriate Finder to use. If opts.URL is provided, we use
 // a DirectAssetFinder. Otherwise we use a GithubAssetFinder. When a Github
 // repo is provided, we assume the repo name is the 'tool' name (for direct
 // URLs, the tool name is unknown and remains empty).
 func getFinder(project string, opts *Flags) (finder Finder, tool string) {
-	if IsLocalFile(project) || (IsUrl(project) && !IsGithubUrl(project)) {
+	if IsGitlabUrl(project) && !IsLocalFile(project) {
+		host, repo := gitlabProject(project)
+		tool = path.Base(repo)
+
+		tag := "latest"
+		if opts.Tag != "" {
+			tag = opts.Tag
+		}
+
+		var mint time.Time
+		if opts.UpgradeOnly {
+			mint = bintime(tool, opts.Output)
+		}
+
+		finder = &GitlabAssetFinder{
+			Host:    host,
+			Project: repo,
+			Tag:     tag,
+			MinTime: mint,
+		}
+	} else if base, repo, ok := giteaProject(project, opts.GiteaHosts); ok && !IsLocalFile(project) {
+		tool = path.Base(repo)
+
+		tag := "latest"
+		if opts.Tag != "" {
+			tag = opts.Tag
+		}
+
+		var mint time.Time
+		if opts.UpgradeOnly {
+			mint = bintime(tool, opts.Output)
+		}
+
+		finder = &GiteaAssetFinder{
+			Base:    base,
+			Repo:    repo,
+			Tag:     tag,
+			MinTime: mint,
+		}
+	} else if IsLocalFile(project) || (IsUrl(project) && !IsGithubUrl(project)) {
 		finder = &DirectAssetFinder{
 			URL: project,
 		}
@@ -135,13 +274,18 @@ func getFinder(project string, opts *Flags) (finder Finder, tool string) {
 	return finder, tool
 }
 
-func getVerifier(sumAsset string, opts *Flags) (verifier Verifier, err error) {
+func getVerifier(sumAsset, sumsAsset, asset string, opts *Flags) (verifier Verifier, err error) {
 	if opts.Verify != "" {
 		verifier, err = NewSha256Verifier(opts.Verify)
 	} else if sumAsset != "" {
 		verifier = &Sha256AssetVerifier{
 			AssetURL: sumAsset,
 		}
+	} else if sumsAsset != "" {
+		verifier = &Sha256SumsVerifier{
+			AssetURL: sumsAsset,
+			Asset:    path.Base(asset),
+		}
 	} else if opts.Hash {
 		verifier = &Sha256Printer{}
 	} else {
@@ -165,7 +309,13 @@ func getDetector(opts *Flags) (detector Detector, err error) {
 		}
 		system, err = NewSystemDetector(split[0], split[1])
 	} else {
-		system, err = NewSystemDetector(runtime.GOOS, runtime.GOARCH)
+		var sd *SystemDetector
+		sd, err = NewSystemDetector(runtime.GOOS, runtime.GOARCH)
+		if err == nil {
+			// glibc builds do not run on musl systems such as Alpine
+			sd.Libc, _ = hostLibc()
+		}
+		system = sd
 	}
 
 	if len(opts.Asset) >= 1 {
@@ -226,8 +376,12 @@ func writeFile(data []byte, rename string, mode fs.FileMode) error {
 		return err
 	}
 
-	// remove file if it exists already
-	os.Remove(rename)
+	// remove file if it exists already. A running executable cannot be
+	// removed on Windows, but it can be renamed out of the way.
+	if err := os.Remove(rename); err != nil && !os.IsNotExist(err) && runtime.GOOS == "windows" {
+		os.Remove(rename + ".old")
+		os.Rename(rename, rename+".old")
+	}
 	// make parent directories if necessary
 	os.MkdirAll(filepath.Dir(rename), 0755)
 
@@ -267,6 +421,35 @@ func userSelect(choices []interface{}) int {
 	return choice
 }
 
+// isTerminal returns true if f is connected to a terminal.
+func isTerminal(f *os.File) bool {
+	fi, err := f.Stat()
+	return err == nil && fi.Mode()&os.ModeCharDevice != 0
+}
+
+// Ask the user a yes/no question and return true if they answered yes. When
+// stdin is not a terminal there is nobody to ask, so the answer is no; --yes
+// must be given to change files unattended.
+func confirm(question string) bool {
+	if !isTerminal(os.Stdin) {
+		fmt.Fprintf(os.Stderr, "%s cannot ask: standard input is not a terminal (use --yes to confirm)\n", question)
+		return false
+	}
+	fmt.Fprintf(os.Stderr, "%s [y/N] ", question)
+	var answer string
+	fmt.Scanln(&answer)
+	answer = strings.ToLower(strings.TrimSpace(answer))
+	return answer == "y" || answer == "yes"
+}
+
+// valueOr returns s, or def if s is empty.
+func valueOr(s, def string) string {
+	if s == "" {
+		return def
+	}
+	return s
+}
+
 func bintime(bin string, to string) (t time.Time) {
 	file := ""
 	dir := "."
@@ -295,9 +478,49 @@ func bintime(bin string, to string) (t time.Time) {
 	return fi.ModTime()
 }
 
-func downloadConfigRepositories(config *Config) error {
-	hasError := false
-	errorList := []error{}
+// lookupTool finds the tracked tool named by target, exiting with an error if
+// there is none.
+func lookupTool(target string) Tool {
+	if target == "" {
+		fatal("no target given")
+	}
+	tools, err := ReadTools()
+	if err != nil {
+		fatal(err)
+	}
+	for i := range tools {
+		if matchesTool(tools[i], target) {
+			statTool(&tools[i])
+		}
+	}
+	t, ok := FindTool(tools, target)
+	if !ok {
+		exit(ExitNoMatch, fmt.Sprintf("`%s` is not tracked in the log", target))
+	}
+	return t
+}
+
+func downloadConfigRepositories(config *Config, format string) error {
+	names := make([]string, 0, len(config.Repositories))
+	for name := range config.Repositories {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+
+	runs := make([][]string, len(names))
+	for i, name := range names {
+		runs[i] = []string{name}
+	}
+	return downloadTargets(runs, format)
+}
+
+// downloadTargets runs eget once for each entry of runs, with the entry as
+// its arguments; the last argument is the target. Each run gets its share of
+// the --limit-rate. With the json-stream
+// format, an event is written to stdout when each target starts, finishes or
+// fails.
+func downloadTargets(runs [][]string, format string) error {
+	events := NewEventWriter(os.Stdout, format)
 
 	binary, err := os.Executable()
 
@@ -305,19 +528,84 @@ func downloadConfigRepositories(config *Config) error {
 		binary = os.Args[0]
 	}
 
-	for name, _ := range config.Repositories {
-		cmd := exec.Command(binary, name)
+	// up to max_downloads targets are downloaded at the same time; their
+	// output is printed when each finishes so it does not interleave
+	concurrent := opts.MaxDownloads > 1 && len(runs) > 1
+	// a batch shows its progress, unless it is reported with events
+	progress := events == nil && len(runs) > 1 && !opts.Quiet
+	// each target is downloaded by its own process with its own limiter,
+	// so the rate limit is divided between the targets downloaded at once;
+	// it is always passed so the children do not apply limit_rate in full
+	slots := opts.MaxDownloads
+	if slots > len(runs) {
+		slots = len(runs)
+	}
+	if slots < 1 {
+		slots = 1
+	}
+	limit := opts.LimitRate / int64(slots)
+	if opts.LimitRate > 0 && limit == 0 {
+		limit = 1
+	}
+	var mu sync.Mutex
+	failed := make([]error, len(runs))
+	cancelled, skipped := false, 0
+	done := cancelOnInterrupt(func() {
+		mu.Lock()
+		cancelled = true
+		mu.Unlock()
+	})
+	defer done()
+	parallelLimit(len(runs), opts.MaxDownloads, func(i int) {
+		args := runs[i]
+		name := args[len(args)-1]
+		mu.Lock()
+		if cancelled {
+			skipped++
+			events.Emit(EventSkipped, name, i+1, len(runs), nil)
+			mu.Unlock()
+			return
+		}
+		if progress {
+			fmt.Fprintf(os.Stderr, "[%d/%d] %s\n", i+1, len(runs), name)
+		}
+		events.Emit(EventStarted, name, i+1, len(runs), nil)
+		mu.Unlock()
+
+		args = append([]string{"--limit-rate", strconv.FormatInt(limit, 10)}, args...)
+		if opts.Root != "" {
+			args = append([]string{"--root", opts.Root}, args...)
+		}
+		cmd := exec.Command(binary, args...)
+		var output bytes.Buffer
 		cmd.Stderr = os.Stderr
+		if concurrent {
+			cmd.Stderr = &output
+		}
 
 		err := cmd.Run()
+		mu.Lock()
+		defer mu.Unlock()
+		os.Stderr.Write(output.Bytes())
+		if err != nil {
+			failed[i] = fmt.Errorf("%s: %w", name, err)
+			if progress {
+				fmt.Fprintf(os.Stderr, "[%d/%d] %s failed: %v\n", i+1, len(runs), name, err)
+			}
+			events.Emit(EventFailed, name, i+1, len(runs), err)
+		} else {
+			events.Emit(EventFinished, name, i+1, len(runs), nil)
+		}
+	})
+
+	errorList := []error{}
+	for _, err := range failed {
 		if err != nil {
-			hasError = true
 			errorList = append(errorList, err)
 		}
 	}
-
-	if hasError {
-		return fmt.Errorf("one or more errors occurred while downloading: %v", errorList)
+	if len(errorList) > 0 || skipped > 0 {
+		return &DownloadError{Errs: errorList, Skipped: skipped}
 	}
 
 	return nil
@@ -326,6 +614,9 @@ func downloadConfigRepositories(config *Config) error {
 var opts Flags
 
 func main() {
+	defer recoverCrash()
+	handleInterrupts()
+
 	var cli CliFlags
 
 	flagparser := flags.NewParser(&cli, flags.PassDoubleDash|flags.PrintErrors)
@@ -336,6 +627,10 @@ func main() {
 		os.Exit(1)
 	}
 
+	if yes, err := strconv.ParseBool(os.Getenv("EGET_ASSUME_YES")); err == nil && yes {
+		cli.Yes = true
+	}
+
 	if cli.Version {
 		fmt.Println("eget version", Version)
 		os.Exit(0)
@@ -346,6 +641,22 @@ func main() {
 		os.Exit(0)
 	}
 
+	if cli.Completion != "" {
+		err := WriteCompletion(os.Stdout, cli.Completion)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if len(cli.Host) > 0 {
+		status, err := RunRemote(cli.Host, remoteArgs(os.Args[1:]))
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(status)
+	}
+
 	config, err := InitializeConfig()
 	if err != nil {
 		fatal(err)
@@ -356,6 +667,13 @@ func main() {
 		fatal(err)
 	}
 
+	if cli.Login {
+		if err := Login(); err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
 	if cli.Rate {
 		rdat, err := GetRateLimit()
 		if err != nil {
@@ -373,106 +691,846 @@ func main() {
 		os.Exit(0)
 	}
 
-	target := ""
-
-	if len(args) > 0 {
-		target = args[0]
+	if cli.PathSnippet != "" {
+		dir := installDir()
+		if dir == "" {
+			fatal("no install directory: set $EGET_BIN or use --to")
+		}
+		snippet, _, err := PathSnippet(cli.PathSnippet, dir)
+		if err != nil {
+			fatal(err)
+		}
+		fmt.Print(snippet)
+		os.Exit(0)
 	}
 
-	err = SetProjectOptionsFromConfig(config, flagparser, &opts, cli, target)
-	if err != nil {
-		fatal(err)
+	if cli.Doctor {
+		if PrintChecks(os.Stdout, Doctor()) > 0 {
+			os.Exit(ExitProblems)
+		}
+		os.Exit(0)
 	}
 
-	if cli.DownloadAll {
-		err = downloadConfigRepositories(config)
+	if cli.Prune {
+		statuses := []string{}
+		if cli.Missing {
+			statuses = append(statuses, StatusMissing, StatusBroken)
+		}
+		if cli.Removed {
+			statuses = append(statuses, StatusRemoved)
+		}
+		if len(statuses) == 0 {
+			statuses = []string{StatusMissing, StatusBroken, StatusRemoved}
+		}
 
+		pruned, malformed, err := PruneLog(statuses, true)
 		if err != nil {
 			fatal(err)
 		}
+		if cli.DryRun || (len(pruned) == 0 && malformed == 0) {
+			for _, t := range pruned {
+				fmt.Printf("Would prune `%s` (%s)\n", t.Path, t.Status)
+			}
+			if malformed > 0 {
+				fmt.Printf("Would drop %d malformed lines\n", malformed)
+			}
+			os.Exit(0)
+		}
 
+		if err := CheckLogWritable(); err != nil {
+			fatal(err)
+		}
+		if !cli.Yes && !confirm(fmt.Sprintf("Prune %d entries and %d malformed lines from the log?", len(pruned), malformed)) {
+			exit(ExitAborted, "aborted")
+		}
+		pruned, malformed, err = PruneLog(statuses, false)
+		if err != nil {
+			fatal(err)
+		}
+		for _, t := range pruned {
+			fmt.Printf("Pruned `%s` (%s)\n", t.Path, t.Status)
+		}
+		if malformed > 0 {
+			fmt.Printf("Dropped %d malformed lines\n", malformed)
+		}
 		os.Exit(0)
 	}
 
-	if len(args) <= 0 {
-		fmt.Println("no target given")
-		flagparser.WriteHelp(os.Stdout)
+	if cli.Duplicates {
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		err = PrintDuplicates(os.Stdout, FindDuplicates(FilterTools(tools, cli.Filter)), cli.Format)
+		if err != nil {
+			fatal(err)
+		}
 		os.Exit(0)
 	}
 
-	if opts.DisableSSL {
-		fmt.Fprintln(os.Stderr, "warning: SSL verification is disabled")
+	if cli.Stats {
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		entries, err := ReadLogs()
+		if err != nil {
+			fatal(err)
+		}
+		err = PrintStats(os.Stdout, ComputeStats(tools, entries), cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
 	}
 
-	if opts.Remove {
-		ebin := os.Getenv("EGET_BIN")
-		removePath := filepath.Join(ebin, target)
-		err := os.Remove(removePath)
+	if cli.Audit {
+		tools, err := LoadTools()
 		if err != nil {
-			fmt.Fprintln(os.Stderr, err)
-			os.Exit(1)
+			fatal(err)
 		}
-		fmt.Printf("Removed `%s`\n", removePath)
-		
-		// Log the removal operation
-		if logErr := LogOperation(target, removePath, "removed"); logErr != nil {
-			fmt.Fprintf(os.Stderr, "Warning: failed to log removal: %v\n", logErr)
+		vulns, skipped, err := Audit(FilterTools(tools, cli.Filter))
+		if err != nil {
+			fatal(err)
+		}
+		for _, t := range skipped {
+			fmt.Fprintf(os.Stderr, "warning: cannot audit %s: no release version recorded\n", t.Name)
+		}
+		err = PrintVulnerabilities(os.Stdout, vulns, cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		if len(vulns) > 0 {
+			os.Exit(ExitProblems)
 		}
-		
 		os.Exit(0)
 	}
 
-	// when --quiet is passed, send non-essential output to io.Discard
-	var output io.Writer = os.Stderr
-	if opts.Quiet {
-		output = io.Discard
+	if cli.Export {
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		err = ExportManifest(os.Stdout, FilterTools(tools, cli.Filter), cli.Pin, time.Now())
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
 	}
 
-	finder, tool := getFinder(target, &opts)
-	assets, err := finder.Find()
-	if err != nil {
-		if errors.Is(err, ErrNoUpgrade) {
-			fmt.Fprintf(output, "%s: %v\n", target, err)
-			os.Exit(0)
+	if cli.Sync {
+		remote, err := NewSyncRemote(config)
+		if err != nil {
+			fatal(err)
+		}
+		data, err := remote.Pull()
+		if err != nil {
+			fatal(err)
 		}
-		fatal(err)
-	}
 
-	detector, err := getDetector(&opts)
-	if err != nil {
-		fatal(err)
-	}
+		// the remote manifest is written to a file so it can be loaded like a
+		// config file
+		cacheDir, err := GetCacheDir()
+		if err != nil {
+			fatal(err)
+		}
+		manifestPath := filepath.Join(cacheDir, "sync-manifest.toml")
+		if err := os.MkdirAll(cacheDir, 0755); err != nil {
+			fatal(err)
+		}
+		if err := os.WriteFile(manifestPath, data, 0644); err != nil {
+			fatal(err)
+		}
+		manifest, err := LoadManifest(manifestPath)
+		if err != nil {
+			fatal(err)
+		}
 
-	// get the url and candidates from the detector
-	url, candidates, err := detector.Detect(assets)
-	if len(candidates) != 0 && err != nil {
-		// if multiple candidates are returned, the user must select manually which one to download
-		fmt.Fprintf(os.Stderr, "%v: please select manually\n", err)
-		choices := make([]interface{}, len(candidates))
-		for i := range candidates {
-			choices[i] = path.Base(candidates[i])
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		plan := PlanSync(manifest, tools)
+		if len(plan.Install) == 0 && len(plan.Publish) == 0 {
+			fmt.Fprintln(os.Stderr, "Already in sync")
+			os.Exit(0)
+		}
+		PrintSyncPlan(os.Stderr, plan)
+		if cli.DryRun {
+			os.Exit(0)
+		}
+		if !cli.Yes && !confirm("Apply these changes?") {
+			exit(ExitAborted, "aborted")
 		}
-		choice := userSelect(choices)
-		url = candidates[choice-1]
-	} else if err != nil {
-		fatal(err)
-	}
 
-	// print the URL
-	fmt.Fprintf(output, "%s\n", url)
+		if len(plan.Install) > 0 {
+			runs := make([][]string, len(plan.Install))
+			for i, repo := range plan.Install {
+				runs[i] = append(manifestArgs(manifest, repo), repo)
+			}
+			if err := downloadTargets(runs, cli.Format); err != nil {
+				fatal(err)
+			}
+		}
+		if len(plan.Publish) > 0 {
+			merged, err := mergeManifest(data, plan.Publish, time.Now())
+			if err != nil {
+				fatal(err)
+			}
+			if err := remote.Push(merged); err != nil {
+				fatal(err)
+			}
+			fmt.Fprintf(os.Stderr, "Published %d repos to the remote manifest\n", len(plan.Publish))
+		}
+		os.Exit(0)
+	}
 
-	// download with progress bar
-	buf := &bytes.Buffer{}
-	err = Download(url, buf, func(size int64) *pb.ProgressBar {
-		var pbout io.Writer = os.Stderr
-		if opts.Quiet {
-			pbout = io.Discard
+	if cli.Starred {
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
 		}
-		return pb.NewOptions64(size,
-			pb.OptionSetWriter(pbout),
-			pb.OptionShowBytes(true),
-			pb.OptionSetWidth(10),
-			pb.OptionThrottle(65*time.Millisecond),
+		repos, err := StarredRepos(tools)
+		if err != nil {
+			fatal(err)
+		}
+		err = PrintStarred(os.Stdout, repos, cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+
+		runs := [][]string{}
+		for _, r := range repos {
+			if !r.Installed {
+				runs = append(runs, []string{r.FullName})
+			}
+		}
+		// installing is only done on request
+		if len(runs) == 0 || cli.DryRun {
+			os.Exit(0)
+		}
+		if !cli.Yes && !confirm(fmt.Sprintf("Install the %d repos that are not installed?", len(runs))) {
+			exit(ExitAborted, "aborted")
+		}
+		if err := downloadTargets(runs, cli.Format); err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Licenses {
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		tools = FilterTools(tools, cli.Filter)
+		repos := make([]string, len(tools))
+		for i, t := range tools {
+			repos[i] = t.Repo
+		}
+		licenses, errs := Licenses(repos)
+		for _, err := range errs {
+			fmt.Fprintln(os.Stderr, "warning:", err)
+		}
+		err = PrintLicenses(os.Stdout, GroupByLicense(tools, licenses), cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.SBOM != "" {
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		err = WriteSBOM(os.Stdout, FilterTools(tools, cli.Filter), cli.SBOM)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Clean {
+		dir := installDir()
+		if dir == "" {
+			fatal("no install directory: set $EGET_BIN or use --to")
+		}
+		orphans, err := OrphanedFiles(dir)
+		if err != nil {
+			fatal(err)
+		}
+		if len(orphans) == 0 {
+			fmt.Printf("No untracked executables in `%s`\n", dir)
+			os.Exit(0)
+		}
+		for _, o := range orphans {
+			fmt.Println(o)
+		}
+
+		// deleting files eget did not install is only done on request
+		if cli.DryRun {
+			os.Exit(0)
+		}
+		if !cli.Yes && !confirm(fmt.Sprintf("Remove %d untracked files?", len(orphans))) {
+			exit(ExitAborted, "aborted")
+		}
+		for _, o := range orphans {
+			if err := removeFile(rootPath(o)); err != nil {
+				fatal(err)
+			}
+			fmt.Printf("Removed `%s`\n", o)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Backup != "" {
+		err := Backup(cli.Backup, config)
+		if err != nil {
+			fatal(err)
+		}
+		fmt.Printf("Backed up eget state to `%s`\n", cli.Backup)
+		os.Exit(0)
+	}
+
+	if cli.Restore != "" {
+		configPath := config.Meta.Path
+		if configPath == "" {
+			homePath, _ := os.UserHomeDir()
+			configPath = GetOSConfigPath(homePath)
+		}
+		if !cli.Yes && !confirm("Replace the current log and config file?") {
+			exit(ExitAborted, "aborted")
+		}
+		restored, err := Restore(cli.Restore, configPath)
+		for _, p := range restored {
+			fmt.Printf("Restored `%s`\n", p)
+		}
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Outdated || cli.UpdateAll {
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		updates, errs := CheckUpdates(FilterTools(tools, cli.Filter), config)
+		for _, err := range errs {
+			fmt.Fprintln(os.Stderr, "warning:", err)
+		}
+
+		if cli.Outdated {
+			err = PrintUpdates(os.Stdout, updates, cli.Format)
+			if err != nil {
+				fatal(err)
+			}
+			notifyUpdates(0, 0, len(updates))
+			if len(updates) > 0 {
+				os.Exit(ExitUpdates)
+			} else if len(errs) > 0 {
+				os.Exit(ExitError)
+			}
+			os.Exit(0)
+		}
+
+		if len(updates) == 0 {
+			fmt.Fprintln(os.Stderr, "All tracked tools are up to date")
+		}
+		runs := make([][]string, len(updates))
+		for i, u := range updates {
+			fmt.Fprintf(os.Stderr, "Updating %s to %s\n", u.Name, u.Latest)
+			runs[i] = updateArgs(u)
+		}
+		err = downloadTargets(runs, cli.Format)
+		failed := failedCount(err)
+		notifyUpdates(len(runs)-failed, failed, 0)
+		if err != nil {
+			fatal(err)
+		}
+		if len(errs) > 0 {
+			os.Exit(ExitError)
+		}
+		os.Exit(0)
+	}
+
+	if cli.List {
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		err = PrintTools(os.Stdout, FilterTools(tools, cli.Filter), cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	target := ""
+
+	if len(args) > 0 {
+		target = args[0]
+	}
+
+	if cli.Search {
+		if len(args) == 0 {
+			fatal("no search query given")
+		}
+		repos, err := SearchRepos(strings.Join(args, " "))
+		if err != nil {
+			fatal(err)
+		}
+		err = PrintRepos(os.Stdout, repos, cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		if len(repos) == 0 {
+			os.Exit(ExitNoMatch)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Add != "" {
+		if target == "" {
+			fatal("no repo given (must be of the form `user/repo` or the URL of a project or download)")
+		}
+		// accept every repo an install accepts
+		if !IsUrl(target) && repoURL(normalizeRepo(target)) == "" {
+			fatal("invalid repo (must be of the form `user/repo` or the URL of a project or download)")
+		}
+		p := cli.Add
+		if opts.Root == "" {
+			if p, err = filepath.Abs(p); err != nil {
+				fatal(err)
+			}
+		} else if !filepath.IsAbs(p) {
+			fatal(fmt.Sprintf("`%s` is not an absolute path inside --root", p))
+		}
+		if fi, err := os.Stat(rootPath(p)); err != nil {
+			fatal(err)
+		} else if fi.IsDir() {
+			fatal(fmt.Sprintf("`%s` is a directory", p))
+		}
+		err = LogOperation(target, p, StatusInstalled, opts.Tag, "")
+		if err != nil {
+			fatal(err)
+		}
+		fmt.Printf("Added `%s` from %s\n", p, target)
+		os.Exit(0)
+	}
+
+	if cli.Diff {
+		manifest := config
+		if target != "" {
+			manifest, err = LoadManifest(target)
+			if err != nil {
+				fatal(err)
+			}
+		}
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		err = PrintDifferences(os.Stdout, DiffManifest(manifest, tools), cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Apply {
+		if target == "" {
+			fatal("--apply needs the path or URL of a manifest")
+		}
+		manifestPath := target
+		if IsUrl(target) {
+			manifestPath, err = FetchManifest(target)
+			if err != nil {
+				fatal(err)
+			}
+		}
+		manifest, err := LoadManifest(manifestPath)
+		if err != nil {
+			fatal(err)
+		}
+		tools, err := LoadTools()
+		if err != nil {
+			fatal(err)
+		}
+		runs := ApplyTargets(manifest, tools)
+		if len(runs) == 0 {
+			fmt.Fprintln(os.Stderr, "Everything in the manifest is installed")
+			os.Exit(0)
+		}
+		verb := "Installing"
+		if cli.DryRun {
+			verb = "Would install"
+		}
+		// each repo is shown with the exact options it is installed with
+		for _, run := range runs {
+			repo := run[len(run)-1]
+			fmt.Fprintf(os.Stderr, "%s %s", verb, repo)
+			if len(run) > 1 {
+				fmt.Fprintf(os.Stderr, " (%s)", joinArgs(run[:len(run)-1]))
+			}
+			fmt.Fprintln(os.Stderr)
+		}
+		if cli.DryRun {
+			os.Exit(0)
+		}
+		// a manifest published by someone else is only installed once the
+		// preview above has been accepted
+		if IsUrl(target) && !cli.Yes && !confirm("Install these repos?") {
+			exit(ExitAborted, "aborted")
+		}
+
+		err = downloadTargets(runs, cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Which {
+		t := lookupTool(target)
+		if t.Status != StatusInstalled {
+			exit(ExitNoMatch, fmt.Sprintf("`%s` is %s", t.Name, t.Status))
+		}
+		fmt.Println(t.Path)
+		os.Exit(0)
+	}
+
+	if cli.Disable || cli.Enable {
+		t := lookupTool(target)
+		if cli.Disable {
+			err = DisableTool(t)
+		} else {
+			err = EnableTool(t)
+		}
+		if err != nil {
+			fatal(err)
+		}
+		if cli.Disable {
+			fmt.Printf("Disabled `%s` (renamed to `%s`)\n", t.Path, t.Path+disabledSuffix)
+		} else {
+			fmt.Printf("Enabled `%s`\n", t.Path)
+		}
+		os.Exit(0)
+	}
+
+	if cli.History {
+		t := lookupTool(target)
+		err = PrintHistory(os.Stdout, t.History, cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Exec {
+		t := lookupTool(target)
+		if t.Status != StatusInstalled {
+			exit(ExitNoMatch, fmt.Sprintf("`%s` is %s", t.Name, t.Status))
+		}
+		if logErr := LogOperation(t.Repo, t.Path, ActionUsed, t.Version, ""); logErr != nil {
+			warnLog("use", logErr)
+		}
+
+		cmd := exec.Command(t.Path, args[1:]...)
+		cmd.Stdin = os.Stdin
+		cmd.Stdout = os.Stdout
+		cmd.Stderr = os.Stderr
+		status, err := runForeground(cmd)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(status)
+	}
+
+	if cli.Info {
+		t := lookupTool(target)
+		info, errs := NewToolInfo(t, config)
+		for _, err := range errs {
+			fmt.Fprintln(os.Stderr, "warning:", err)
+		}
+		err = PrintToolInfo(os.Stdout, info, cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Contents {
+		t := lookupTool(target)
+		url, entries, err := ArchiveContents(t)
+		if errors.Is(err, ErrNotCached) {
+			exit(ExitNoMatch, fmt.Sprintf("the release asset of `%s` is not in the download cache; reinstall it to download it again", t.Name))
+		} else if err != nil {
+			fatal(err)
+		}
+		err = PrintContents(os.Stdout, url, entries, cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.Wizard {
+		if !isTerminal(os.Stdin) {
+			fatal("--wizard needs a terminal to ask its questions")
+		}
+		args := RunWizard(target)
+		fmt.Fprintf(os.Stderr, "\n  %s\n\n", commandLine(args))
+		if !confirm("Run this command?") {
+			exit(ExitAborted, "aborted")
+		}
+		status, err := RunEget(args)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(status)
+	}
+
+	if cli.SelfUpdate {
+		exe, err := os.Executable()
+		if err == nil {
+			exe, err = filepath.EvalSymlinks(exe)
+		}
+		if err != nil {
+			fatal(err)
+		}
+		target = selfRepo
+		args = []string{target}
+
+		// set the flags as well so a config section for the repo cannot
+		// override them
+		upgradeOnly := true
+		opts.Output, cli.Output = exe, &exe
+		opts.UpgradeOnly, cli.UpgradeOnly = upgradeOnly, &upgradeOnly
+	}
+
+	err = SetProjectOptionsFromConfig(config, flagparser, &opts, cli, target)
+	if err != nil {
+		fatal(err)
+	}
+
+	if cli.FromFile != "" {
+		runs, err := ReadTargets(cli.FromFile)
+		if err != nil {
+			fatal(err)
+		}
+		err = downloadTargets(runs, cli.Format)
+		if err != nil {
+			fatal(err)
+		}
+		os.Exit(0)
+	}
+
+	if cli.DownloadAll {
+		err = downloadConfigRepositories(config, cli.Format)
+
+		if err != nil {
+			fatal(err)
+		}
+
+		os.Exit(0)
+	}
+
+	if len(args) <= 0 {
+		fmt.Println("no target given")
+		flagparser.WriteHelp(os.Stdout)
+		os.Exit(0)
+	}
+
+	if opts.DisableSSL {
+		fmt.Fprintln(os.Stderr, "warning: SSL verification is disabled")
+	}
+
+	if opts.Remove {
+		ebin := os.Getenv("EGET_BIN")
+		removePath := filepath.Join(ebin, target)
+		if _, err := os.Stat(removePath); err != nil && runtime.GOOS == "windows" && filepath.Ext(removePath) == "" {
+			removePath += ".exe"
+		}
+		repo := target
+
+		// prefer the path recorded in the log if the target is a tracked tool
+		if tools, err := LoadTools(); err == nil {
+			if t, ok := FindTool(tools, target); ok && t.Status != StatusRemoved {
+				removePath = t.Path
+				repo = t.Repo
+				// removing it without logging would leave it listed as missing
+				if err := CheckLogWritable(); err != nil {
+					fatal(err)
+				}
+			}
+		}
+
+		warnRunning(rootPath(removePath), "removing")
+		if !cli.Yes && !confirm(fmt.Sprintf("Remove `%s`?", removePath)) {
+			exit(ExitAborted, "aborted")
+		}
+
+		err := removeFile(rootPath(removePath))
+		escalated := ""
+		if esc := offerEscalation(err, fmt.Sprintf("Delete `%s` permanently", removePath)); esc != "" {
+			escalated = esc
+			err = removeEscalated(esc, rootPath(removePath))
+		}
+		if os.IsNotExist(err) {
+			exit(ExitNoMatch, err)
+		} else if err != nil {
+			fmt.Fprintln(os.Stderr, err)
+			os.Exit(1)
+		}
+		if escalated != "" {
+			fmt.Printf("Removed `%s` with %s\n", removePath, escalated)
+		} else if opts.Permanent {
+			fmt.Printf("Removed `%s`\n", removePath)
+		} else {
+			fmt.Printf("Moved `%s` to the trash\n", removePath)
+		}
+		
+		// Log the removal operation
+		if logErr := LogOperation(repo, removePath, "removed", "", ""); logErr != nil {
+			warnLog("removal", logErr)
+		}
+		
+		os.Exit(0)
+	}
+
+	// when --quiet is passed, send non-essential output to io.Discard
+	var output io.Writer = os.Stderr
+	if opts.Quiet {
+		output = io.Discard
+	}
+
+	if cli.Binstall {
+		crate, err := FindCrate(target, "")
+		if err != nil {
+			fatal(err)
+		}
+		if crate == nil {
+			exit(ExitNoMatch, fmt.Sprintf("%s is not published on crates.io", target))
+		}
+		dir := opts.Output
+		if dir == "" {
+			dir = os.Getenv("EGET_BIN")
+		}
+		// cargo-binstall names the binary after the crate in most cases
+		out := filepath.Join(dir, crate.Name)
+		if runtime.GOOS == "windows" {
+			out += ".exe"
+		}
+		if err := checkRootPath(out); err != nil {
+			fatal(err)
+		}
+		action := "installed"
+		if _, err := os.Stat(rootPath(out)); err == nil {
+			action = "updated"
+		}
+
+		fmt.Fprintf(output, "Installing crate %s with cargo-binstall\n", crate.Name)
+		if err := Binstall(crate, rootPath(dir), opts.Tag); err != nil {
+			fatal(err)
+		}
+		if _, err := os.Stat(rootPath(out)); dir != "" && err == nil {
+			if logErr := LogOperation(target, out, action, opts.Tag, ""); logErr != nil {
+				warnLog("operation", logErr)
+			}
+		}
+		os.Exit(0)
+	}
+
+	finder, tool := getFinder(target, &opts)
+	assets, err := finder.Find()
+	if err != nil {
+		if errors.Is(err, ErrNoUpgrade) {
+			fmt.Fprintf(output, "%s: %v\n", target, err)
+			os.Exit(0)
+		}
+		fatal(err)
+	}
+
+	// the release tag is recorded in the log when it is known
+	version := ""
+	switch f := finder.(type) {
+	case ReleaseFinder:
+		version = f.ReleaseTag()
+	case *GithubSourceFinder:
+		version = f.Tag
+	case *DirectAssetFinder:
+		if IsUrl(f.URL) {
+			version = urlVersion(f.URL)
+		}
+	}
+
+	// without filters of its own, a repo gets the asset the user chose last
+	remembered := false
+	if len(opts.Asset) == 0 {
+		opts.Asset = rememberedAsset(target)
+		remembered = len(opts.Asset) > 0
+	}
+
+	detector, err := getDetector(&opts)
+	if err != nil {
+		fatal(err)
+	}
+
+	// get the url and candidates from the detector
+	url, candidates, err := detector.Detect(assets)
+	if remembered && err != nil && len(candidates) == 0 {
+		// the release no longer has an asset like the chosen one
+		opts.Asset = nil
+		if detector, err = getDetector(&opts); err != nil {
+			fatal(err)
+		}
+		url, candidates, err = detector.Detect(assets)
+	}
+	if len(candidates) != 0 && err != nil {
+		// if multiple candidates are returned, the user must select manually which one to download
+		fmt.Fprintf(os.Stderr, "%v: please select manually\n", err)
+		var sizes map[string]int64
+		if f, ok := finder.(*GithubAssetFinder); ok {
+			sizes = f.Sizes
+		}
+		choices := make([]interface{}, len(candidates))
+		for i := range candidates {
+			choices[i] = path.Base(candidates[i])
+			if size, ok := sizes[candidates[i]]; ok {
+				choices[i] = fmt.Sprintf("%s (%s)", path.Base(candidates[i]), formatSize(size))
+			}
+		}
+		choice := userSelect(choices)
+		url = candidates[choice-1]
+		if toolSource(target) == SourceRelease {
+			if err := rememberAsset(target, path.Base(url), version); err != nil {
+				fmt.Fprintln(os.Stderr, "warning: cannot remember the chosen asset:", err)
+			}
+		}
+	} else if err != nil {
+		fatal(err)
+	}
+
+	// print the URL
+	fmt.Fprintf(output, "%s\n", url)
+
+	// download with progress bar
+	buf := &bytes.Buffer{}
+	err = Download(url, buf, func(size int64) *pb.ProgressBar {
+		// the progress bar redraws itself with control characters, which
+		// only make sense on a terminal
+		var pbout io.Writer = os.Stderr
+		if opts.Quiet || !isTerminal(os.Stderr) {
+			pbout = io.Discard
+		}
+		return pb.NewOptions64(size,
+			pb.OptionSetWriter(pbout),
+			pb.OptionShowBytes(true),
+			pb.OptionSetWidth(10),
+			pb.OptionThrottle(65*time.Millisecond),
 			pb.OptionShowCount(),
 			pb.OptionSpinnerType(14),
 			pb.OptionFullWidth(),
@@ -494,13 +1552,24 @@ func main() {
 
 	body := buf.Bytes()
 
+	// a checksum file for the asset alone is preferred over one covering the
+	// whole release
 	sumAsset := checksumAsset(url, assets)
-	verifier, err := getVerifier(sumAsset, &opts)
+	sumsAsset := ""
+	if sumAsset == "" {
+		sumsAsset = checksumsAsset(assets)
+	}
+	verifier, err := getVerifier(sumAsset, sumsAsset, url, &opts)
 	if err != nil {
 		fatal(err)
 	}
+	if sumAsset == "" {
+		sumAsset = sumsAsset
+	}
 	err = verifier.Verify(body)
-	if err != nil {
+	if errors.Is(err, ErrNotListed) {
+		fmt.Fprintf(os.Stderr, "warning: checksum not verified: %s is not listed in %s\n", path.Base(url), path.Base(sumAsset))
+	} else if err != nil {
 		fatal(err)
 	} else if opts.Verify == "" && sumAsset != "" {
 		fmt.Fprintf(output, "Checksum verified with %s\n", path.Base(sumAsset))
@@ -508,6 +1577,38 @@ func main() {
 		fmt.Fprintf(output, "Checksum verified\n")
 	}
 
+	// signatures are only checked for repos configured with a public key, in
+	// which case a missing signature is an error
+	verified := []string{}
+	if opts.PublicKey != "" {
+		sigAsset, kind := signatureAsset(url, assets)
+		if sigAsset == "" {
+			fatal(fmt.Sprintf("a public key is configured but no signature was found for %s", path.Base(url)))
+		}
+		err = VerifySignature(body, url, sigAsset, kind, opts.PublicKey)
+		if err != nil {
+			fatal(err)
+		}
+		verified = append(verified, kind)
+		fmt.Fprintf(output, "Signature verified with %s\n", path.Base(sigAsset))
+	}
+
+	if opts.Provenance {
+		gf, ok := finder.(*GithubAssetFinder)
+		if !ok {
+			fatal("provenance can only be verified for GitHub releases")
+		}
+		kind, err := VerifyProvenance(body, url, assets, gf.Repo)
+		if err != nil {
+			fatal(err)
+		}
+		verified = append(verified, kind)
+		fmt.Fprintf(output, "Provenance verified with %s\n", kind)
+	}
+	if err := downloadVerified(url); err != nil {
+		fmt.Fprintln(os.Stderr, "warning: cannot record the download in the cache:", err)
+	}
+
 	extractor, err := getExtractor(url, tool, &opts)
 	if err != nil {
 		fatal(err)
@@ -536,6 +1637,7 @@ func main() {
 		bins = []ExtractedFile{bin}
 	}
 
+	warnedPath := false
 	extract := func(bin ExtractedFile) {
 		mode := bin.Mode()
 
@@ -544,10 +1646,12 @@ func main() {
 		out := filepath.Base(bin.Name)
 		if opts.Output == "-" {
 			out = "-"
-		} else if opts.Output != "" && IsDirectory(opts.Output) {
+		} else if opts.Output != "" && IsDirectory(rootPath(opts.Output)) {
 			out = filepath.Join(opts.Output, out)
 		} else if opts.Output != "" && opts.All {
-			os.MkdirAll(opts.Output, 0755)
+			if err := os.MkdirAll(rootPath(opts.Output), 0755); err != nil {
+				fatal(err)
+			}
 			out = filepath.Join(opts.Output, out)
 		} else {
 			if opts.Output != "" {
@@ -562,25 +1666,93 @@ func main() {
 			}
 		}
 
+		if err := checkRootPath(out); err != nil {
+			fatal(err)
+		}
+		// a symlink is kept by writing to the file it links to, while the
+		// install is still logged under the path of the link
+		file := rootPath(out)
+		if out != "-" {
+			if li, err := os.Lstat(file); err == nil && li.Mode()&os.ModeSymlink != 0 {
+				if resolved, err := filepath.EvalSymlinks(file); err == nil {
+					file = resolved
+				}
+			}
+		}
+
 		// Check if file already exists (for update detection)
 		action := "installed"
-		if _, err := os.Stat(out); err == nil {
+		if _, err := os.Stat(file); err == nil {
 			action = "updated"
+			warnRunning(file, "updating")
+		}
+
+		// keep the current binary until the new one is known to work
+		backup := ""
+		done := func() {}
+		if action == "updated" && !bin.Dir {
+			backup = backupBinary(file)
+		}
+		if backup != "" {
+			done = onInterrupt(func() {
+				if restoreBinary(backup, file) == nil {
+					fmt.Fprintf(os.Stderr, "Restored the previous `%s`\n", out)
+				}
+			})
 		}
 
-		err = bin.Extract(out)
+		err = bin.Extract(file)
+		if !bin.Dir && out != "-" {
+			// binaries in system directories can be installed as root
+			if esc := offerEscalation(err, fmt.Sprintf("Install `%s`", out)); esc != "" {
+				err = extractEscalated(esc, bin, file)
+			}
+		}
+		if err == nil && backup != "" && mode&0111 != 0 && opts.System == "" && opts.Root == "" {
+			if problems := binaryProblems(out); len(problems) > 0 {
+				err = fmt.Errorf("the new `%s` cannot run on this system: %s", out, strings.Join(problems, "; "))
+			}
+		}
+		done()
 		if err != nil {
+			if backup != "" {
+				if rerr := restoreBinary(backup, file); rerr != nil {
+					fmt.Fprintf(os.Stderr, "Warning: failed to restore the previous `%s` from `%s`: %v\n", out, backup, rerr)
+				} else {
+					fmt.Fprintf(os.Stderr, "Restored the previous `%s`\n", out)
+				}
+			}
 			fatal(err)
 		}
+		if backup != "" {
+			os.Remove(backup)
+		}
 
 		fmt.Fprintf(output, "Extracted `%s` to `%s`\n", bin.ArchiveName, out)
+		if archs := archMismatch(out); out != "-" && len(archs) > 0 && opts.System == "" && opts.Root == "" {
+			fmt.Fprintf(os.Stderr, "Warning: `%s` is built for %s and cannot run on this %s system\n", out, strings.Join(archs, ", "), runtime.GOARCH)
+		}
+
+		// Gatekeeper blocks quarantined binaries that are not notarized
+		if out != "-" && !bin.Dir && !opts.Quarantine {
+			if err := clearQuarantine(file); err != nil {
+				fmt.Fprintf(os.Stderr, "Warning: failed to clear the quarantine attribute: %v\n", err)
+			}
+		}
 		
 		// Log the operation (skip logging to stdout)
 		if out != "-" {
-			if logErr := LogOperation(target, out, action); logErr != nil {
-				fmt.Fprintf(os.Stderr, "Warning: failed to log operation: %v\n", logErr)
+			if logErr := LogOperation(target, out, action, version, strings.Join(verified, ",")); logErr != nil {
+				warnLog("operation", logErr)
 			}
 		}
+
+		// binaries put in the install directory are meant to be run by name,
+		// which cannot be checked for another root
+		if dir := installDir(); opts.Root == "" && dir != "" && filepath.Dir(out) == filepath.Clean(dir) && !onPath(dir) && !warnedPath {
+			warnedPath = true
+			fmt.Fprintf(os.Stderr, "Warning: %s is not on PATH\n  fix: %s\n", dir, pathFix(dir))
+		}
 	}
 
 	if opts.All {
@@ -0,0 +1,244 @@
+package main
+
+import (
+	"bytes"
+	"debug/elf"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strconv"
+	"strings"
+	"sync"
+)
+
+// Kinds of C library an ELF binary can be linked against.
+const (
+	LibcGlibc = "glibc"
+	LibcMusl  = "musl"
+)
+
+// An ELFInfo describes what an ELF binary needs from the system to run.
+type ELFInfo struct {
+	Interp  string   // dynamic loader, "" if statically linked
+	Libc    string   // LibcGlibc, LibcMusl or "" if static or unknown
+	Glibc   string   // newest GLIBC_x.y symbol version required, without the prefix
+	Needed  []string // shared libraries the binary is linked against
+	RunPath []string // directories the binary asks the loader to search first
+}
+
+// readELF inspects the binary at path. It returns nil if the file is not an
+// ELF binary.
+func readELF(path string) (*ELFInfo, error) {
+	f, err := elf.Open(path)
+	if _, ok := err.(*elf.FormatError); ok {
+		return nil, nil
+	} else if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+
+	info := &ELFInfo{}
+	for _, p := range f.Progs {
+		if p.Type != elf.PT_INTERP {
+			continue
+		}
+		data := make([]byte, p.Filesz)
+		if _, err := p.ReadAt(data, 0); err != nil {
+			return nil, err
+		}
+		info.Interp = string(bytes.TrimRight(data, "\x00"))
+	}
+	switch base := filepath.Base(info.Interp); {
+	case strings.HasPrefix(base, "ld-musl"):
+		info.Libc = LibcMusl
+	case strings.HasPrefix(base, "ld-linux"), strings.HasPrefix(base, "ld64.so"):
+		info.Libc = LibcGlibc
+	}
+
+	info.Needed, _ = f.ImportedLibraries()
+	for _, tag := range []elf.DynTag{elf.DT_RUNPATH, elf.DT_RPATH} {
+		paths, _ := f.DynString(tag)
+		for _, p := range paths {
+			for _, dir := range filepath.SplitList(p) {
+				info.RunPath = append(info.RunPath, strings.ReplaceAll(dir, "$ORIGIN", filepath.Dir(path)))
+			}
+		}
+	}
+
+	// statically linked binaries have no imported symbols
+	syms, _ := f.ImportedSymbols()
+	for _, s := range syms {
+		if v := strings.TrimPrefix(s.Version, "GLIBC_"); v != s.Version && compareVersions(v, info.Glibc) > 0 {
+			info.Glibc = v
+		}
+	}
+	return info, nil
+}
+
+// compareVersions compares dotted numeric versions such as "2.34" and
+// returns -1, 0 or 1. Parts that are not numbers compare as 0, and the empty
+// version is the oldest.
+func compareVersions(a, b string) int {
+	if a == b {
+		return 0
+	} else if b == "" {
+		return 1
+	} else if a == "" {
+		return -1
+	}
+	as, bs := strings.Split(a, "."), strings.Split(b, ".")
+	for i := 0; i < len(as) || i < len(bs); i++ {
+		var x, y int
+		if i < len(as) {
+			x, _ = strconv.Atoi(as[i])
+		}
+		if i < len(bs) {
+			y, _ = strconv.Atoi(bs[i])
+		}
+		if x != y {
+			if x < y {
+				return -1
+			}
+			return 1
+		}
+	}
+	return 0
+}
+
+var (
+	hostLibcOnce    sync.Once
+	hostLibcKind    string
+	hostLibcVersion string
+)
+
+// hostLibc returns the C library of this system (LibcGlibc or LibcMusl) and
+// its version, if it is known. It returns "" on systems other than Linux.
+func hostLibc() (string, string) {
+	hostLibcOnce.Do(func() {
+		if runtime.GOOS != "linux" {
+			return
+		}
+		if musl, _ := filepath.Glob("/lib/ld-musl-*.so.1"); len(musl) > 0 {
+			hostLibcKind = LibcMusl
+			return
+		}
+		hostLibcKind = LibcGlibc
+		// prints "glibc 2.35"
+		if out, err := exec.Command("getconf", "GNU_LIBC_VERSION").Output(); err == nil {
+			if fields := strings.Fields(string(out)); len(fields) == 2 {
+				hostLibcVersion = fields[1]
+			}
+		}
+	})
+	return hostLibcKind, hostLibcVersion
+}
+
+// binaryProblems returns the reasons the binary at path cannot run on this
+// system, such as an architecture it was not built for, a C library it was
+// not built for or a shared library that is not installed.
+func binaryProblems(path string) []string {
+	if archs := archMismatch(path); len(archs) > 0 {
+		return []string{fmt.Sprintf("built for %s, but this system is %s", strings.Join(archs, ", "), runtime.GOARCH)}
+	}
+	if runtime.GOOS != "linux" {
+		return nil
+	}
+	info, err := readELF(path)
+	if err != nil || info == nil {
+		return nil
+	}
+
+	problems := []string{}
+	libc, version := hostLibc()
+	switch {
+	case info.Libc == LibcGlibc && libc == LibcMusl:
+		problems = append(problems, "built for glibc, but this system uses musl")
+	case info.Libc == LibcGlibc && version != "" && compareVersions(info.Glibc, version) > 0:
+		problems = append(problems, fmt.Sprintf("needs GLIBC_%s, but this system has glibc %s", info.Glibc, version))
+	case info.Libc == LibcMusl && libc == LibcGlibc:
+		if _, err := os.Stat(info.Interp); err != nil {
+			problems = append(problems, fmt.Sprintf("built for musl, but its loader %s is not installed", info.Interp))
+		}
+	}
+	// with the wrong C library, its libraries are missing too
+	if len(problems) > 0 {
+		return problems
+	}
+
+	for _, lib := range info.Needed {
+		if !findLibrary(lib, info.RunPath) {
+			problems = append(problems, fmt.Sprintf("needs the shared library %s, which is not installed", lib))
+		}
+	}
+	return problems
+}
+
+var (
+	libraryCacheOnce sync.Once
+	libraryCache     map[string]bool
+	libraryDirs      []string
+)
+
+// loadLibraryCache reads the libraries known to the dynamic loader from
+// `ldconfig -p`, and the directories it searches from ld.so.conf (glibc) or
+// ld-musl-*.path (musl), along with the default ones.
+func loadLibraryCache() {
+	libraryCache = make(map[string]bool)
+	// lines look like "	libz.so.1 (libc6,x86-64) => /lib/x86_64-linux-gnu/libz.so.1"
+	ldconfig := "ldconfig"
+	if _, err := exec.LookPath(ldconfig); err != nil {
+		// not on the PATH of regular users on some distributions
+		ldconfig = "/sbin/ldconfig"
+	}
+	if out, err := exec.Command(ldconfig, "-p").Output(); err == nil {
+		for _, line := range strings.Split(string(out), "\n") {
+			if fields := strings.Fields(line); len(fields) > 0 && strings.Contains(line, "=>") {
+				libraryCache[fields[0]] = true
+			}
+		}
+	}
+
+	libraryDirs = []string{"/lib", "/lib64", "/usr/lib", "/usr/lib64", "/usr/local/lib"}
+	confs := []string{"/etc/ld.so.conf"}
+	more, _ := filepath.Glob("/etc/ld.so.conf.d/*.conf")
+	confs = append(confs, more...)
+	musl, _ := filepath.Glob("/etc/ld-musl-*.path")
+	confs = append(confs, musl...)
+	for _, conf := range confs {
+		data, err := os.ReadFile(conf)
+		if err != nil {
+			continue
+		}
+		for _, line := range strings.Split(string(data), "\n") {
+			line = strings.TrimSpace(line)
+			if line == "" || strings.HasPrefix(line, "#") || strings.HasPrefix(line, "include") {
+				continue
+			}
+			// musl path files may list several directories on one line
+			for _, dir := range strings.FieldsFunc(line, func(r rune) bool { return r == ':' || r == ' ' }) {
+				libraryDirs = append(libraryDirs, dir)
+			}
+		}
+	}
+}
+
+// findLibrary returns true if the dynamic loader can find the shared library
+// lib in runPath, $LD_LIBRARY_PATH, its cache or its directories.
+func findLibrary(lib string, runPath []string) bool {
+	libraryCacheOnce.Do(loadLibraryCache)
+	if libraryCache[lib] {
+		return true
+	}
+	dirs := append(append(append([]string{}, runPath...), filepath.SplitList(os.Getenv("LD_LIBRARY_PATH"))...), libraryDirs...)
+	for _, dir := range dirs {
+		if dir == "" {
+			continue
+		}
+		if _, err := os.Stat(filepath.Join(dir, lib)); err == nil {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,38 @@
+package main
+
+import (
+	"crypto/x509"
+	"errors"
+	"fmt"
+	"io/fs"
+	"net/http"
+	"path/filepath"
+)
+
+// fixFor returns a suggested fix for common classes of errors, in the style
+// of the fixes printed by --doctor, or "" if there is none.
+func fixFor(err error) string {
+	var ge *GithubError
+	var unknownCA x509.UnknownAuthorityError
+	var le *LogError
+	var rl *RateLimitError
+	switch {
+	case errors.Is(err, ErrOffline):
+		return "run the command again without --offline"
+	case errors.As(err, &ge) && (ge.Code == http.StatusForbidden || ge.Code == http.StatusTooManyRequests):
+		return "set a GitHub token with `github_token` in the config file or $EGET_GITHUB_TOKEN, or run `eget --login`; see `eget --rate`"
+	case errors.As(err, &rl) && rl.Host == "api.github.com":
+		return fmt.Sprintf("wait until %s, or set a GitHub token with `github_token` in the config file or $EGET_GITHUB_TOKEN, or run `eget --login`", rl.Reset.Local().Format("15:04"))
+	case errors.As(err, &rl):
+		return fmt.Sprintf("wait until %s", rl.Reset.Local().Format("15:04"))
+	case errors.As(err, &ge) && ge.Code == http.StatusNotFound:
+		return "check the spelling of the repo and that it publishes releases, or use --tag for an existing tag"
+	case errors.As(err, &unknownCA):
+		return "set `ca_file` in the config file (or $EGET_CA_FILE) to the root certificate of your network"
+	case errors.As(err, &le) && errors.Is(le.Err, fs.ErrPermission):
+		return fmt.Sprintf("make the log and its directory %s writable by your user, or ask the owner of the install to", filepath.Dir(le.Path))
+	case errors.Is(err, fs.ErrPermission):
+		return "make the file writable by your user, or install to a directory you own with --to"
+	}
+	return ""
+}
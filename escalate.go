@@ -0,0 +1,86 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+)
+
+// escalators are the commands that can run another command as root, in order
+// of preference.
+var escalators = []string{"sudo", "run0", "doas", "pkexec"}
+
+// escalator returns the first command in escalators that is on PATH, or "" if
+// there is none or privileges cannot be escalated on this system.
+func escalator() string {
+	if runtime.GOOS == "windows" {
+		return ""
+	}
+	for _, e := range escalators {
+		if _, err := exec.LookPath(e); err == nil {
+			return e
+		}
+	}
+	return ""
+}
+
+// offerEscalation returns the escalator to retry an operation that failed
+// with err, after asking the user. It returns "" if err is not a permission
+// error, there is no escalator, or the user declines. The user is only asked
+// when stdin is a terminal, since the escalator asks for a password.
+func offerEscalation(err error, question string) string {
+	if !errors.Is(err, fs.ErrPermission) || !isTerminal(os.Stdin) {
+		return ""
+	}
+	esc := escalator()
+	if esc == "" {
+		return ""
+	}
+	fmt.Fprintln(os.Stderr, err)
+	if !confirm(fmt.Sprintf("%s with %s?", question, esc)) {
+		return ""
+	}
+	return esc
+}
+
+// runEscalated runs name with args as root through esc, connected to the
+// terminal so it can ask for a password.
+func runEscalated(esc string, name string, args ...string) error {
+	cmd := exec.Command(esc, append([]string{name}, args...)...)
+	cmd.Stdin = os.Stdin
+	cmd.Stdout = os.Stderr
+	cmd.Stderr = os.Stderr
+	if err := cmd.Run(); err != nil {
+		return fmt.Errorf("%s %s: %w", esc, name, err)
+	}
+	return nil
+}
+
+// removeEscalated permanently deletes the file at path as root.
+func removeEscalated(esc, path string) error {
+	return runEscalated(esc, "rm", "-f", "--", path)
+}
+
+// extractEscalated extracts bin to a temporary file and installs it at out as
+// root, keeping its mode.
+func extractEscalated(esc string, bin ExtractedFile, out string) error {
+	dir, err := os.MkdirTemp("", "eget-")
+	if err != nil {
+		return err
+	}
+	defer os.RemoveAll(dir)
+
+	tmp := filepath.Join(dir, filepath.Base(out))
+	if err := bin.Extract(tmp); err != nil {
+		return err
+	}
+	fi, err := os.Stat(tmp)
+	if err != nil {
+		return err
+	}
+	return runEscalated(esc, "install", "-m", fmt.Sprintf("%o", fi.Mode().Perm()), "--", tmp, out)
+}
@@ -0,0 +1,60 @@
+package main
+
+import (
+	"encoding/json"
+	"io"
+	"time"
+)
+
+// Events reported by the json-stream output format.
+const (
+	EventStarted  = "started"
+	EventFinished = "finished"
+	EventFailed   = "failed"
+	EventSkipped  = "skipped"
+)
+
+// An Event reports the progress of one step of a long-running operation, such
+// as installing one of several targets. With --format json-stream, each event
+// is written to stdout as a single line of JSON.
+type Event struct {
+	Event  string    `json:"event"`
+	Target string    `json:"target"`
+	Index  int       `json:"index"` // 1-based position of the target
+	Total  int       `json:"total"`
+	Error  string    `json:"error,omitempty"`
+	Time   time.Time `json:"time"`
+}
+
+// An EventWriter writes events as a stream of JSON objects. A nil
+// EventWriter discards all events.
+type EventWriter struct {
+	enc *json.Encoder
+}
+
+// NewEventWriter returns an EventWriter for the given output format, which is
+// nil unless the format is "json-stream".
+func NewEventWriter(w io.Writer, format string) *EventWriter {
+	if format != "json-stream" {
+		return nil
+	}
+	return &EventWriter{enc: json.NewEncoder(w)}
+}
+
+// Emit writes a single event.
+func (ew *EventWriter) Emit(event, target string, index, total int, err error) {
+	if ew == nil {
+		return
+	}
+	e := Event{
+		Event:  event,
+		Target: target,
+		Index:  index,
+		Total:  total,
+		Time:   time.Now().UTC(),
+	}
+	if err != nil {
+		e.Error = err.Error()
+	}
+	ew.enc.Encode(e)
+}
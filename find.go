@@ -6,6 +6,8 @@ import (
 	"fmt"
 	"io"
 	"net/http"
+	"net/url"
+	"regexp"
 	"strings"
 	"time"
 )
@@ -15,10 +17,18 @@ type Finder interface {
 	Find() ([]string, error)
 }
 
+// A ReleaseFinder is a Finder for the assets of a hosted release. After a
+// successful Find, ReleaseTag returns the tag of the release that was found.
+type ReleaseFinder interface {
+	Finder
+	ReleaseTag() string
+}
+
 // A GithubRelease matches the Assets portion of Github's release API json.
 type GithubRelease struct {
 	Assets []struct {
 		DownloadURL string `json:"browser_download_url"`
+		Size        int64  `json:"size"`
 	} `json:"assets"`
 
 	Prerelease bool      `json:"prerelease"`
@@ -53,7 +63,9 @@ type GithubAssetFinder struct {
 	Repo       string
 	Tag        string
 	Prerelease bool
-	MinTime    time.Time // release must be after MinTime to be found
+	MinTime    time.Time        // release must be after MinTime to be found
+	Version    string           // tag of the release that was found, set by Find
+	Sizes      map[string]int64 // sizes of the assets of that release by URL, set by Find
 }
 
 var ErrNoUpgrade = errors.New("requested release is not more recent than current version")
@@ -107,49 +119,38 @@ func (f *GithubAssetFinder) Find() ([]string, error) {
 	if release.CreatedAt.Before(f.MinTime) {
 		return nil, ErrNoUpgrade
 	}
+	f.Version = release.Tag
+
+	return f.releaseAssets(release), nil
+}
 
-	// accumulate all assets from the json into a slice
-	assets := make([]string, 0, len(release.Assets))
-	for _, a := range release.Assets {
+// releaseAssets returns the URLs of the assets of r, recording their sizes.
+func (f *GithubAssetFinder) releaseAssets(r GithubRelease) []string {
+	f.Sizes = make(map[string]int64, len(r.Assets))
+	assets := make([]string, 0, len(r.Assets))
+	for _, a := range r.Assets {
 		assets = append(assets, a.DownloadURL)
+		f.Sizes[a.DownloadURL] = a.Size
 	}
+	return assets
+}
 
-	return assets, nil
+func (f *GithubAssetFinder) ReleaseTag() string {
+	return f.Version
 }
 
+// releasesPerPage is the number of releases requested per page when looking
+// for a tag, the most the GitHub API returns. Pages are only requested until
+// the tag is found, and each one is cached with its ETag, so looking up an old
+// release of a project with hundreds of them costs a few conditional requests.
+const releasesPerPage = 100
+
 func (f *GithubAssetFinder) FindMatch() ([]string, error) {
 	tag := f.Tag[len("tags/"):]
 
 	for page := 1; ; page++ {
-		url := fmt.Sprintf("https://api.github.com/repos/%s/releases?page=%d", f.Repo, page)
-		resp, err := Get(url)
-		if err != nil {
-			return nil, err
-		}
-
-		defer resp.Body.Close()
-
-		if resp.StatusCode != http.StatusOK {
-			body, err := io.ReadAll(resp.Body)
-			if err != nil {
-				return nil, err
-			}
-			return nil, &GithubError{
-				Status: resp.Status,
-				Code:   resp.StatusCode,
-				Body:   body,
-				Url:    url,
-			}
-		}
-
-		// read and unmarshal the resulting json
-		body, err := io.ReadAll(resp.Body)
-		if err != nil {
-			return nil, err
-		}
-
-		var releases []GithubRelease
-		err = json.Unmarshal(body, &releases)
+		url := fmt.Sprintf("https://api.github.com/repos/%s/releases?per_page=%d&page=%d", f.Repo, releasesPerPage, page)
+		releases, err := getReleases(url)
 		if err != nil {
 			return nil, err
 		}
@@ -160,15 +161,12 @@ func (f *GithubAssetFinder) FindMatch() ([]string, error) {
 			}
 			if strings.Contains(r.Tag, tag) && !r.CreatedAt.Before(f.MinTime) {
 				// we have a winner
-				assets := make([]string, 0, len(r.Assets))
-				for _, a := range r.Assets {
-					assets = append(assets, a.DownloadURL)
-				}
-				return assets, nil
+				f.Version = r.Tag
+				return f.releaseAssets(r), nil
 			}
 		}
 
-		if len(releases) < 30 {
+		if len(releases) < releasesPerPage {
 			break
 		}
 	}
@@ -176,6 +174,32 @@ func (f *GithubAssetFinder) FindMatch() ([]string, error) {
 	return nil, fmt.Errorf("no matching tag for '%s'", tag)
 }
 
+// getReleases returns a page of releases from the GitHub API at url.
+func getReleases(url string) ([]GithubRelease, error) {
+	resp, err := Get(url)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, &GithubError{
+			Status: resp.Status,
+			Code:   resp.StatusCode,
+			Body:   body,
+			Url:    url,
+		}
+	}
+
+	var releases []GithubRelease
+	err = json.Unmarshal(body, &releases)
+	return releases, err
+}
+
 // finds the latest pre-release and returns the tag
 func (f *GithubAssetFinder) getLatestTag() (string, error) {
 	url := fmt.Sprintf("https://api.github.com/repos/%s/releases", f.Repo)
@@ -202,6 +226,143 @@ func (f *GithubAssetFinder) getLatestTag() (string, error) {
 	return releases[0].Tag, nil
 }
 
+// A GitlabRelease matches the parts of GitLab's release API json used by
+// eget.
+type GitlabRelease struct {
+	Tag        string    `json:"tag_name"`
+	ReleasedAt time.Time `json:"released_at"`
+	Assets     struct {
+		Links []struct {
+			URL            string `json:"url"`
+			DirectAssetURL string `json:"direct_asset_url"`
+		} `json:"links"`
+	} `json:"assets"`
+}
+
+// A GitlabAssetFinder finds the assets of a release of a GitLab project.
+// Project is the full path of the project, such as 'owner/repo' or
+// 'group/subgroup/repo'. Tag is the release tag, or 'latest'.
+type GitlabAssetFinder struct {
+	Host    string
+	Project string
+	Tag     string
+	MinTime time.Time // release must be after MinTime to be found
+	Version string    // tag of the release that was found, set by Find
+}
+
+func (f *GitlabAssetFinder) Find() ([]string, error) {
+	release := "permalink/latest"
+	if f.Tag != "latest" {
+		release = url.PathEscape(f.Tag)
+	}
+	api := fmt.Sprintf("https://%s/api/v4/projects/%s/releases/%s", f.Host, url.PathEscape(f.Project), release)
+	resp, err := Get(api)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, &GithubError{
+			Status: resp.Status,
+			Code:   resp.StatusCode,
+			Body:   body,
+			Url:    api,
+		}
+	}
+
+	var rel GitlabRelease
+	err = json.Unmarshal(body, &rel)
+	if err != nil {
+		return nil, err
+	}
+
+	if rel.ReleasedAt.Before(f.MinTime) {
+		return nil, ErrNoUpgrade
+	}
+	f.Version = rel.Tag
+
+	// release links may point to generic packages or external hosts, the
+	// direct asset URL is preferred when there is one
+	assets := make([]string, 0, len(rel.Assets.Links))
+	for _, l := range rel.Assets.Links {
+		if l.DirectAssetURL != "" {
+			assets = append(assets, l.DirectAssetURL)
+		} else {
+			assets = append(assets, l.URL)
+		}
+	}
+	return assets, nil
+}
+
+func (f *GitlabAssetFinder) ReleaseTag() string {
+	return f.Version
+}
+
+// A GiteaAssetFinder finds the assets of a release of a repository hosted on
+// a Gitea or Forgejo instance, such as Codeberg. Base is the URL of the
+// instance and Repo has the form 'owner/repo'. Tag is the release tag, or
+// 'latest'.
+type GiteaAssetFinder struct {
+	Base    string
+	Repo    string
+	Tag     string
+	MinTime time.Time // release must be after MinTime to be found
+	Version string    // tag of the release that was found, set by Find
+}
+
+func (f *GiteaAssetFinder) Find() ([]string, error) {
+	release := "latest"
+	if f.Tag != "latest" {
+		release = "tags/" + url.PathEscape(f.Tag)
+	}
+	api := fmt.Sprintf("%s/api/v1/repos/%s/releases/%s", f.Base, f.Repo, release)
+	resp, err := Get(api)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, &GithubError{
+			Status: resp.Status,
+			Code:   resp.StatusCode,
+			Body:   body,
+			Url:    api,
+		}
+	}
+
+	// Gitea's release json uses the same field names as Github's
+	var rel GithubRelease
+	err = json.Unmarshal(body, &rel)
+	if err != nil {
+		return nil, err
+	}
+
+	if rel.CreatedAt.Before(f.MinTime) {
+		return nil, ErrNoUpgrade
+	}
+	f.Version = rel.Tag
+
+	assets := make([]string, 0, len(rel.Assets))
+	for _, a := range rel.Assets {
+		assets = append(assets, a.DownloadURL)
+	}
+	return assets, nil
+}
+
+func (f *GiteaAssetFinder) ReleaseTag() string {
+	return f.Version
+}
+
 // A DirectAssetFinder returns the embedded URL directly as the only asset.
 type DirectAssetFinder struct {
 	URL string
@@ -211,6 +372,25 @@ func (f *DirectAssetFinder) Find() ([]string, error) {
 	return []string{f.URL}, nil
 }
 
+var urlVersionRgx = regexp.MustCompile(`v?\d+(\.\d+)+`)
+
+// urlVersion returns the version embedded in the file name or path of a
+// direct download URL, such as 'v1.2.3' in
+// https://example.com/v1.2.3/tool-linux-amd64.tar.gz, or "" if there is none.
+// The last version-like component wins, since hosts usually put the version
+// closest to the file name.
+func urlVersion(u string) string {
+	parsed, err := url.Parse(u)
+	if err != nil {
+		return ""
+	}
+	matches := urlVersionRgx.FindAllString(parsed.Path, -1)
+	if len(matches) == 0 {
+		return ""
+	}
+	return matches[len(matches)-1]
+}
+
 type GithubSourceFinder struct {
 	Tool string
 	Repo string
@@ -1,22 +1,34 @@
 package main
 
 type Flags struct {
-	Tag         string
-	Prerelease  bool
-	Source      bool
-	Output      string
-	System      string
-	ExtractFile string
-	All         bool
-	Quiet       bool
-	DLOnly      bool
-	UpgradeOnly bool
-	Asset       []string
-	Hash        bool
-	Verify      string
-	Remove      bool
-	DisableSSL  bool
-	ShowLog     bool
+	Tag          string
+	Prerelease   bool
+	Source       bool
+	Output       string
+	System       string
+	ExtractFile  string
+	All          bool
+	Quiet        bool
+	DLOnly       bool
+	UpgradeOnly  bool
+	Asset        []string
+	Hash         bool
+	Verify       string
+	Remove       bool
+	DisableSSL   bool
+	ShowLog      bool
+	GiteaHosts   []string
+	PublicKey    string
+	Provenance   bool
+	Offline      bool
+	Notify       bool
+	Root         string
+	Permanent    bool
+	Quarantine   bool
+	MaxRequests  int
+	MaxDownloads int
+	DLCache      string
+	LimitRate    int64
 }
 
 type CliFlags struct {
@@ -33,11 +45,59 @@ type CliFlags struct {
 	Asset       *[]string `short:"a" long:"asset" description:"download a specific asset containing the given string; can be specified multiple times for additional filtering; use ^ for anti-match"`
 	Hash        *bool     `long:"sha256" description:"show the SHA-256 hash of the downloaded asset"`
 	Verify      *string   `long:"verify-sha256" description:"verify the downloaded asset checksum against the one provided"`
+	Binstall    bool      `long:"binstall" description:"install the target's crates.io crate with cargo-binstall instead of downloading its release"`
+	Provenance  *bool     `long:"verify-provenance" description:"verify the build provenance of GitHub release assets with gh or cosign"`
+	Login       bool      `long:"login" description:"log in to GitHub with the GitHub CLI's device flow, so no token needs to be configured"`
 	Rate        bool      `long:"rate" description:"show GitHub API rate limiting information"`
 	Remove      *bool     `short:"r" long:"remove" description:"remove the given file from $EGET_BIN or the current directory"`
+	Permanent   *bool     `long:"permanent" description:"with --remove or --clean, delete files permanently instead of moving them to the trash"`
 	Version     bool      `short:"v" long:"version" description:"show version information"`
 	Help        bool      `short:"h" long:"help" description:"show this help message"`
 	DownloadAll bool      `short:"D" long:"download-all" description:"download all projects defined in the config file"`
+	Root        string    `long:"root" description:"read the log and install binaries relative to the given filesystem root, such as a container image or chroot"`
+	Host        []string  `long:"host" description:"run eget with the other options on the given [user@]host over ssh; can be specified multiple times"`
+	Offline     bool      `long:"offline" description:"never access the network; use cached data where there is some"`
+	LimitRate   *string   `long:"limit-rate" description:"limit the download speed to the given bytes per second, such as 500K or 2M"`
 	DisableSSL  *bool     `short:"k" long:"disable-ssl" description:"disable SSL verification for download requests"`
+	FromFile    string    `long:"from-file" description:"install every target listed in the given file, or stdin if '-'"`
+	SelfUpdate  bool      `long:"self-update" description:"update eget itself to its latest release"`
 	ShowLog     bool      `long:"log" description:"show the installation log"`
+	List        bool      `long:"list" description:"list the binaries tracked in the installation log"`
+	Filter      string    `long:"filter" description:"only consider tracked binaries whose name or repo contains the given string"`
+	Format      string    `long:"format" description:"output format: table, json, tsv or porcelain for listings; json-stream for --download-all, --from-file, --update-all and --apply"`
+	Stats       bool      `long:"stats" description:"show aggregate metrics about the tracked binaries"`
+	Duplicates  bool      `long:"duplicates" description:"list tracked binaries with identical contents and the disk space they waste"`
+	Audit       bool      `long:"audit" description:"check the installed versions of tracked binaries for known vulnerabilities on osv.dev"`
+	Outdated    bool      `long:"outdated" description:"list tracked binaries with a newer release available"`
+	UpdateAll   bool      `long:"update-all" description:"update every tracked binary with a newer release available"`
+	Notify      *bool     `long:"notify" description:"with --outdated or --update-all, show a desktop notification summarizing the result"`
+	Doctor      bool      `long:"doctor" description:"check the log, install directory and tracked binaries for problems"`
+	Exec        bool      `long:"exec" description:"run the given tracked binary with the arguments after --"`
+	History     bool      `long:"history" description:"show every logged install, update and removal of the given binary"`
+	Info        bool      `long:"info" description:"show everything known about the given tracked binary"`
+	Contents    bool      `long:"contents" description:"list the files in the cached release archive of the given tracked binary"`
+	Wizard      bool      `long:"wizard" description:"ask for the install options of the given target one at a time, then show and run the eget command"`
+	Diff        bool      `long:"diff" description:"compare the repositories in the given config file (or the current one) with the installed binaries"`
+	Export      bool      `long:"export" description:"print a manifest of the installed binaries, for a dotfiles repository"`
+	Pin         bool      `long:"pin" description:"with --export, pin each repo to its installed release tag"`
+	Apply       bool      `long:"apply" description:"install the repositories in the given manifest (a file or URL) that are missing or at a different pinned version"`
+	Sync        bool      `long:"sync" description:"sync the installed binaries with the manifest in the configured git repository or gist"`
+	Search      bool      `long:"search" description:"search GitHub for repositories with prebuilt release assets"`
+	Starred     bool      `long:"starred" description:"list your starred GitHub repositories with prebuilt release assets, and offer to install the missing ones"`
+	Which       bool      `long:"which" description:"print the path of the given tracked binary"`
+	Disable     bool      `long:"disable" description:"take the given tracked binary off PATH by renaming it to NAME.disabled"`
+	Enable      bool      `long:"enable" description:"restore the given binary disabled with --disable"`
+	Add         string    `long:"add" description:"record an existing binary at the given path in the log as installed from the target repo"`
+	Clean       bool      `long:"clean" description:"list (and optionally remove) executables in the install directory that are not in the log"`
+	Prune       bool      `long:"prune" description:"remove log entries for missing and removed binaries"`
+	Missing     bool      `long:"missing" description:"with --prune, only prune binaries that are missing from disk"`
+	Removed     bool      `long:"removed" description:"with --prune, only prune binaries that were removed"`
+	DryRun      bool      `long:"dry-run" description:"show what would be done without changing anything"`
+	Licenses    bool      `long:"licenses" description:"list the installed binaries grouped by the license of their repo"`
+	SBOM        string    `long:"sbom" description:"print a software bill of materials for the installed binaries: cyclonedx or spdx"`
+	Backup      string    `long:"backup" description:"save the log and config file to the given archive"`
+	Restore     string    `long:"restore" description:"replace the log and config file with the contents of the given archive"`
+	PathSnippet string    `long:"path-snippet" description:"print a snippet that adds the install directory to PATH for the given shell: bash, zsh or fish"`
+	Completion  string    `long:"completion" description:"print a completion script for the given shell: bash, zsh, fish or powershell"`
+	Yes         bool      `short:"y" long:"yes" description:"do not ask for confirmation before changing files or the log"`
 }
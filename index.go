@@ -0,0 +1,60 @@
+package main
+
+import (
+	"strings"
+)
+
+// indexName is the cache file of the repo index.
+const indexName = "index.json"
+
+// An indexedRepo is what the repo index knows about a repository.
+type indexedRepo struct {
+	Description string   `json:"description,omitempty"`
+	Topics      []string `json:"topics,omitempty"`
+}
+
+// indexRepos adds the descriptions and topics of repos to the repo index, a
+// cache of every repository description eget has fetched, by --search,
+// --starred or license lookups. It lets --filter match tools by what they do
+// without accessing the network.
+func indexRepos(repos []GithubRepo) {
+	if len(repos) == 0 {
+		return
+	}
+	index := make(map[string]indexedRepo)
+	readCache(indexName, &index)
+	for _, r := range repos {
+		index[strings.ToLower(r.FullName)] = indexedRepo{Description: r.Description, Topics: r.Topics}
+	}
+	writeCache(indexName, index)
+}
+
+// readIndex returns the repo index, keyed by the lowercase full name of each
+// GitHub repository.
+func readIndex() map[string]indexedRepo {
+	index := make(map[string]indexedRepo)
+	readCache(indexName, &index)
+	return index
+}
+
+// indexMatches returns true if the description or a topic of repo in index
+// contains filter, which must be lowercase.
+func indexMatches(index map[string]indexedRepo, repo, filter string) bool {
+	u := repoURL(repo)
+	if !strings.HasPrefix(u, "https://github.com/") {
+		return false
+	}
+	r, ok := index[strings.ToLower(strings.TrimPrefix(u, "https://github.com/"))]
+	if !ok {
+		return false
+	}
+	if strings.Contains(strings.ToLower(r.Description), filter) {
+		return true
+	}
+	for _, topic := range r.Topics {
+		if strings.Contains(topic, filter) {
+			return true
+		}
+	}
+	return false
+}
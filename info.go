@@ -0,0 +1,233 @@
+package main
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// A ToolInfo is everything known about a single tracked tool.
+type ToolInfo struct {
+	Tool
+	SHA256  string `json:"sha256,omitempty"`
+	Pinned  string `json:"pinned,omitempty"`
+	License string `json:"license,omitempty"`
+	Crate   *Crate `json:"crate,omitempty"`
+
+	ShadowedBy     string `json:"shadowed_by,omitempty"`     // executable run instead of this one
+	VersionManager string `json:"version_manager,omitempty"` // mise or asdf, if it also provides the command
+	Quarantined    bool   `json:"quarantined,omitempty"`     // has the macOS quarantine attribute
+	CommandOwner   string `json:"command_owner,omitempty"`   // repo whose binary runs for this name, if there are conflicts
+
+	Problems []string `json:"problems,omitempty"` // reasons the binary cannot run on this system
+}
+
+// NewToolInfo gathers the details of t. The pinned tag is read from the
+// repository's section in the config file, if there is one, and the license
+// is looked up on GitHub unless it is cached. The crate is looked up on
+// crates.io, for repos that are also published there. Details that cannot be
+// gathered are left empty and the errors are returned.
+func NewToolInfo(t Tool, config *Config) (ToolInfo, []error) {
+	info := ToolInfo{Tool: t}
+	errs := []error{}
+	if t.Status == StatusInstalled {
+		sums, hashErrs := fileHashes([]string{rootPath(t.Path)})
+		for _, err := range hashErrs {
+			errs = append(errs, fmt.Errorf("checksum: %w", err))
+		}
+		info.SHA256 = sums[0]
+		info.ShadowedBy = shadowedBy(t)
+		info.VersionManager, _ = versionManager(t.Name)
+		info.Quarantined = hasQuarantine(rootPath(t.Path))
+		if opts.Root == "" {
+			info.Problems = binaryProblems(t.Path)
+		}
+	}
+	if len(t.Conflicts) > 0 {
+		if tools, err := ReadTools(); err == nil {
+			info.CommandOwner = commandOwner(tools, t.Name)
+		}
+	}
+	if repo, ok := repoConfig(config, t.Repo); ok {
+		info.Pinned = repo.Tag
+	}
+	licenses, licenseErrs := Licenses([]string{t.Repo})
+	info.License = licenses[t.Repo]
+	errs = append(errs, licenseErrs...)
+	crate, err := FindCrate(t.Repo, t.Name)
+	if err != nil {
+		errs = append(errs, fmt.Errorf("crates.io: %w", err))
+	}
+	info.Crate = crate
+
+	// with --offline, missing details are expected
+	if opts.Offline {
+		online := []error{}
+		for _, err := range errs {
+			if !errors.Is(err, ErrOffline) {
+				online = append(online, err)
+			}
+		}
+		errs = online
+	}
+	return info, errs
+}
+
+// fileSha256 returns the hex encoded SHA-256 checksum of the file at path.
+func fileSha256(path string) (string, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+
+	h := sha256.New()
+	if _, err := io.Copy(h, f); err != nil {
+		return "", err
+	}
+	return hex.EncodeToString(h.Sum(nil)), nil
+}
+
+// hashTTL is how long a cached checksum is used, even for a file whose size
+// and modification time did not change.
+const hashTTL = 30 * 24 * time.Hour
+
+type cachedHash struct {
+	Size    int64     `json:"size"`
+	ModTime time.Time `json:"mod_time"`
+	SHA256  string    `json:"sha256"`
+	Hashed  time.Time `json:"hashed"`
+}
+
+// fileHashes returns the SHA-256 checksum of each file in paths, or "" for
+// an empty path or a file that cannot be read. Files are hashed in parallel,
+// and checksums are cached by path for hashTTL as long as the size and
+// modification time of the file stay the same.
+func fileHashes(paths []string) ([]string, []error) {
+	cache := make(map[string]cachedHash)
+	readCache("hashes.json", &cache)
+
+	sums := make([]string, len(paths))
+	hashed := make([]*cachedHash, len(paths))
+	failed := make([]error, len(paths))
+	parallel(len(paths), func(i int) {
+		if paths[i] == "" {
+			return
+		}
+		fi, err := os.Stat(paths[i])
+		if err != nil {
+			failed[i] = err
+			return
+		}
+		if c, ok := cache[paths[i]]; ok && c.Size == fi.Size() && c.ModTime.Equal(fi.ModTime()) && time.Since(c.Hashed) < hashTTL {
+			sums[i] = c.SHA256
+			return
+		}
+		sum, err := fileSha256(paths[i])
+		if err != nil {
+			failed[i] = err
+			return
+		}
+		sums[i] = sum
+		hashed[i] = &cachedHash{Size: fi.Size(), ModTime: fi.ModTime(), SHA256: sum, Hashed: time.Now()}
+	})
+
+	errs := []error{}
+	changed := false
+	for i, h := range hashed {
+		if failed[i] != nil {
+			errs = append(errs, failed[i])
+		}
+		if h != nil {
+			cache[paths[i]] = *h
+			changed = true
+		}
+	}
+	if changed {
+		if err := writeCache("hashes.json", cache); err != nil {
+			errs = append(errs, err)
+		}
+	}
+	return sums, errs
+}
+
+// PrintToolInfo writes info to w in the given format: "table" (the default,
+// one field per line), "json" or "porcelain".
+func PrintToolInfo(w io.Writer, info ToolInfo, format string) error {
+	switch format {
+	case "", "table":
+		size := "-"
+		if info.Status == StatusInstalled {
+			size = formatSize(info.Size)
+		}
+		pinned := "no"
+		if info.Pinned != "" {
+			pinned = info.Pinned
+		}
+
+		tw := NewTable(w, 1).Wrap()
+		fmt.Fprintf(tw, "Name:\t%s\n", info.Name)
+		fmt.Fprintf(tw, "Repo:\t%s\n", info.Repo)
+		fmt.Fprintf(tw, "Path:\t%s\n", info.Path)
+		if info.Target != "" {
+			fmt.Fprintf(tw, "Links to:\t%s\n", info.Target)
+		}
+		if info.Version != "" {
+			fmt.Fprintf(tw, "Version:\t%s\n", info.Version)
+		}
+		fmt.Fprintf(tw, "Status:\t%s\n", info.Status)
+		for _, p := range info.Problems {
+			fmt.Fprintf(tw, "Problem:\t%s\n", p)
+		}
+		if info.ShadowedBy != "" {
+			fmt.Fprintf(tw, "Shadowed by:\t%s (earlier in PATH)\n", info.ShadowedBy)
+		}
+		if info.VersionManager != "" {
+			fmt.Fprintf(tw, "Also managed by:\t%s\n", info.VersionManager)
+		}
+		if len(info.Conflicts) > 0 {
+			fmt.Fprintf(tw, "Conflicts with:\t%s\n", strings.Join(info.Conflicts, ", "))
+			fmt.Fprintf(tw, "Command owner:\t%s\n", valueOr(info.CommandOwner, "none (not first in PATH)"))
+		}
+		if info.Quarantined {
+			fmt.Fprintf(tw, "Quarantined:\tyes (Gatekeeper may block it; run `xattr -d %s %s`)\n", quarantineAttr, info.Path)
+		}
+		fmt.Fprintf(tw, "Size:\t%s\n", size)
+		fmt.Fprintf(tw, "Installed:\t%s (%s)\n", formatTime(info.Installed), formatRelative(info.Installed))
+		fmt.Fprintf(tw, "Updated:\t%s (%s)\n", formatTime(info.Updated), formatRelative(info.Updated))
+		if !info.LastUsed.IsZero() {
+			fmt.Fprintf(tw, "Last used:\t%s (%s)\n", formatTime(info.LastUsed), formatRelative(info.LastUsed))
+		}
+		if info.SHA256 != "" {
+			fmt.Fprintf(tw, "SHA-256:\t%s\n", info.SHA256)
+		}
+		fmt.Fprintf(tw, "Pinned:\t%s\n", pinned)
+		fmt.Fprintf(tw, "License:\t%s\n", info.License)
+		if info.Crate != nil {
+			fmt.Fprintf(tw, "Crate:\t%s %s (crates.io)\n", info.Crate.Name, info.Crate.Version)
+		}
+		if info.Verified != "" {
+			fmt.Fprintf(tw, "Verified:\t%s\n", strings.ReplaceAll(info.Verified, ",", ", "))
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(info)
+	case "porcelain":
+		crate, crateVersion := "", ""
+		if info.Crate != nil {
+			crate, crateVersion = info.Crate.Name, info.Crate.Version
+		}
+		porcelainTool(w, info.Tool, info.SHA256, info.Pinned, info.Verified, info.License, crate, crateVersion, info.ShadowedBy, info.VersionManager, info.Target, strconv.FormatBool(info.Quarantined), strings.Join(info.Problems, "; "), strings.Join(info.Conflicts, ","), info.CommandOwner)
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
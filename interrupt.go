@@ -0,0 +1,143 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"os/signal"
+	"runtime/debug"
+	"sync"
+	"syscall"
+)
+
+var (
+	cleanupMu   sync.Mutex
+	cleanups    = map[int]func(){}
+	nextCleanup int
+	cancel      func()
+	foreground  *os.Process // child that interrupts are left to
+)
+
+// ErrCancelled is returned by operations on several targets that stopped
+// early because they were cancelled.
+var ErrCancelled = errors.New("cancelled")
+
+// onInterrupt registers f to run if eget is interrupted or crashes before
+// the returned function is called, which unregisters it. It is used to undo
+// changes that would otherwise be left half done, such as restoring a binary
+// while its update is being written.
+func onInterrupt(f func()) func() {
+	cleanupMu.Lock()
+	defer cleanupMu.Unlock()
+	id := nextCleanup
+	nextCleanup++
+	cleanups[id] = f
+	return func() {
+		cleanupMu.Lock()
+		delete(cleanups, id)
+		cleanupMu.Unlock()
+	}
+}
+
+// runCleanups runs and unregisters every function registered with
+// onInterrupt.
+func runCleanups() {
+	cleanupMu.Lock()
+	defer cleanupMu.Unlock()
+	for id, f := range cleanups {
+		f()
+		delete(cleanups, id)
+	}
+}
+
+// cancelOnInterrupt makes the next SIGINT call f, which should make an
+// operation on several targets skip the ones it has not started, instead of
+// exiting.
+// A second SIGINT exits as usual. The returned function unregisters f.
+func cancelOnInterrupt(f func()) func() {
+	cleanupMu.Lock()
+	defer cleanupMu.Unlock()
+	cancel = f
+	return func() {
+		cleanupMu.Lock()
+		cancel = nil
+		cleanupMu.Unlock()
+	}
+}
+
+// runForeground runs cmd and returns its exit status, or 128 plus the signal
+// that killed it. While it runs, interrupts are left to it: a SIGINT from
+// the terminal reaches the whole foreground process group, so the child
+// decides what Ctrl-C does, and a SIGTERM sent to eget is forwarded to it.
+func runForeground(cmd *exec.Cmd) (int, error) {
+	if err := cmd.Start(); err != nil {
+		return ExitError, err
+	}
+	cleanupMu.Lock()
+	foreground = cmd.Process
+	cleanupMu.Unlock()
+	err := cmd.Wait()
+	cleanupMu.Lock()
+	foreground = nil
+	cleanupMu.Unlock()
+
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) {
+		if status, ok := exitErr.Sys().(syscall.WaitStatus); ok && status.Signaled() {
+			return 128 + int(status.Signal()), nil
+		}
+		return exitErr.ExitCode(), nil
+	} else if err != nil {
+		return ExitError, err
+	}
+	return ExitOK, nil
+}
+
+// handleInterrupts runs the registered cleanups and exits with ExitAborted
+// when eget receives SIGINT or SIGTERM, unless a SIGINT cancels an operation
+// registered with cancelOnInterrupt, or a child runs in the foreground (see
+// runForeground). The message starts on a new line, as the signal may arrive
+// while a progress bar is drawn.
+func handleInterrupts() {
+	c := make(chan os.Signal, 1)
+	signal.Notify(c, os.Interrupt, syscall.SIGTERM)
+	go func() {
+		for sig := range c {
+			cleanupMu.Lock()
+			child := foreground
+			cleanupMu.Unlock()
+			if child != nil {
+				if sig != os.Interrupt {
+					child.Signal(sig)
+				}
+				continue
+			}
+
+			cleanupMu.Lock()
+			f := cancel
+			cancel = nil
+			cleanupMu.Unlock()
+			if f != nil && sig == os.Interrupt {
+				fmt.Fprintln(os.Stderr, "\ncancelled; skipping the remaining targets (press Ctrl-C again to abort)")
+				f()
+				continue
+			}
+			fmt.Fprintf(os.Stderr, "\ninterrupted (%v)\n", sig)
+			runCleanups()
+			os.Exit(ExitAborted)
+		}
+	}()
+}
+
+// recoverCrash is deferred by main and by the goroutines it starts, such as
+// the workers of parallelLimit. It runs the registered cleanups if eget
+// panics, and prints the error and stack trace so it can be reported.
+func recoverCrash() {
+	if r := recover(); r != nil {
+		fmt.Fprintf(os.Stderr, "\neget crashed: %v\n", r)
+		runCleanups()
+		fmt.Fprintf(os.Stderr, "%s\nplease report this at https://github.com/zyedidia/eget/issues\n", debug.Stack())
+		os.Exit(ExitError)
+	}
+}
@@ -0,0 +1,177 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"sort"
+	"strings"
+	"time"
+)
+
+// licenseTTL is how long a cached license is used before it is fetched again.
+const licenseTTL = 30 * 24 * time.Hour
+
+// LicenseUnknown is reported for tools whose license could not be determined.
+const LicenseUnknown = "unknown"
+
+type cachedLicense struct {
+	SPDX    string    `json:"spdx"`
+	Fetched time.Time `json:"fetched"`
+}
+
+// fetchLicense returns the SPDX identifier of the license GitHub detected for
+// repo, "NOASSERTION" if the license is not one GitHub knows, or "" if the
+// repo has no license.
+func fetchLicense(repo string) (string, error) {
+	api := fmt.Sprintf("https://api.github.com/repos/%s", repo)
+	resp, err := Get(api)
+	if err != nil {
+		return "", err
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return "", err
+	}
+	if resp.StatusCode != http.StatusOK {
+		return "", &GithubError{
+			Status: resp.Status,
+			Code:   resp.StatusCode,
+			Body:   body,
+			Url:    api,
+		}
+	}
+
+	var info struct {
+		GithubRepo
+		License *struct {
+			SPDX string `json:"spdx_id"`
+		} `json:"license"`
+	}
+	if err := json.Unmarshal(body, &info); err != nil {
+		return "", err
+	}
+	indexRepos([]GithubRepo{info.GithubRepo})
+	if info.License == nil {
+		return "", nil
+	}
+	return info.License.SPDX, nil
+}
+
+// Licenses returns the license of each repo, keyed by repo. Licenses are only
+// known for GitHub repos and are cached for licenseTTL, or for as long as
+// needed with --offline; repos whose license cannot be determined map to
+// LicenseUnknown.
+func Licenses(repos []string) (map[string]string, []error) {
+	cache := make(map[string]cachedLicense)
+	readCache("licenses.json", &cache)
+
+	licenses := make(map[string]string)
+	errs := []error{}
+	changed := false
+	for _, repo := range repos {
+		u := repoURL(repo)
+		if !strings.HasPrefix(u, "https://github.com/") {
+			licenses[repo] = LicenseUnknown
+			continue
+		}
+		name := strings.ToLower(strings.TrimPrefix(u, "https://github.com/"))
+		c, ok := cache[name]
+		if !ok || time.Since(c.Fetched) > licenseTTL {
+			spdx, err := fetchLicense(name)
+			if err != nil && ok && errors.Is(err, ErrOffline) {
+				// a stale license beats none at all
+				licenses[repo] = valueOr(c.SPDX, LicenseUnknown)
+				continue
+			} else if err != nil {
+				errs = append(errs, fmt.Errorf("%s: %w", repo, err))
+				licenses[repo] = LicenseUnknown
+				continue
+			}
+			c = cachedLicense{SPDX: spdx, Fetched: time.Now()}
+			cache[name] = c
+			changed = true
+		}
+		licenses[repo] = c.SPDX
+		if c.SPDX == "" {
+			licenses[repo] = LicenseUnknown
+		}
+	}
+	if changed {
+		if err := writeCache("licenses.json", cache); err != nil {
+			errs = append(errs, err)
+		}
+	}
+	return licenses, errs
+}
+
+// A LicenseGroup is the set of installed tools released under one license.
+type LicenseGroup struct {
+	License string `json:"license"`
+	Tools   []Tool `json:"tools"`
+}
+
+// GroupByLicense groups the installed tools by license, with the groups
+// sorted by license and unknown licenses last.
+func GroupByLicense(tools []Tool, licenses map[string]string) []LicenseGroup {
+	index := make(map[string]int)
+	groups := []LicenseGroup{}
+	for _, t := range tools {
+		if t.Status != StatusInstalled {
+			continue
+		}
+		l := licenses[t.Repo]
+		if l == "" {
+			l = LicenseUnknown
+		}
+		i, ok := index[l]
+		if !ok {
+			i = len(groups)
+			index[l] = i
+			groups = append(groups, LicenseGroup{License: l})
+		}
+		groups[i].Tools = append(groups[i].Tools, t)
+	}
+	sort.Slice(groups, func(i, j int) bool {
+		if (groups[i].License == LicenseUnknown) != (groups[j].License == LicenseUnknown) {
+			return groups[j].License == LicenseUnknown
+		}
+		return groups[i].License < groups[j].License
+	})
+	return groups
+}
+
+// PrintLicenses writes the license groups to w in the given format: "table"
+// (the default), "json" or "porcelain".
+func PrintLicenses(w io.Writer, groups []LicenseGroup, format string) error {
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2)
+		for i, g := range groups {
+			if i > 0 {
+				fmt.Fprintln(tw)
+			}
+			fmt.Fprintf(tw, "%s (%d)\n", g.License, len(g.Tools))
+			for _, t := range g.Tools {
+				fmt.Fprintf(tw, "  %s\t%s\n", t.Name, displayRepo(t))
+			}
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(groups)
+	case "porcelain":
+		for _, g := range groups {
+			for _, t := range g.Tools {
+				writePorcelain(w, g.License, t.Name, t.Repo, t.Path)
+			}
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
@@ -1,11 +1,14 @@
 package main
 
 import (
+	"bytes"
 	"fmt"
+	"io"
 	"os"
 	"path/filepath"
 	"runtime"
 	"strings"
+	"sync"
 	"time"
 
 	"github.com/zyedidia/eget/home"
@@ -13,18 +16,33 @@ import (
 
 // LogEntry represents a single log entry for a binary operation
 type LogEntry struct {
-	Timestamp time.Time
-	Repo      string
-	Path      string
-	Action    string
+	Timestamp time.Time `json:"timestamp"`
+	Repo      string    `json:"repo"`
+	Path      string    `json:"path"`
+	Action    string    `json:"action"`
+	Version   string    `json:"version,omitempty"`  // release tag, empty if unknown
+	Verified  string    `json:"verified,omitempty"` // comma separated kinds of signature and provenance that were verified
 }
 
 // GetLogDir returns the appropriate log directory based on the OS
 func GetLogDir() (string, error) {
-	var logDir string
-	
+	if opts.Root != "" {
+		// a fixed location, as the home directory on this system seldom
+		// exists in a container image or chroot
+		return filepath.Join(opts.Root, "var", "lib", "eget"), nil
+	}
+	dir, err := dataDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(dir, "logs"), nil
+}
+
+// dataDir returns the directory on this system that eget keeps its log and
+// cache in.
+func dataDir() (string, error) {
 	if runtime.GOOS == "windows" {
-		// Windows: use %LOCALAPPDATA%\eget\logs
+		// Windows: use %LOCALAPPDATA%\eget
 		localAppData := os.Getenv("LOCALAPPDATA")
 		if localAppData == "" {
 			homeDir, err := home.Home()
@@ -33,17 +51,14 @@ func GetLogDir() (string, error) {
 			}
 			localAppData = filepath.Join(homeDir, "AppData", "Local")
 		}
-		logDir = filepath.Join(localAppData, "eget", "logs")
-	} else {
-		// Unix-like systems: use ~/.local/share/eget/logs
-		homeDir, err := home.Home()
-		if err != nil {
-			return "", fmt.Errorf("could not determine home directory: %w", err)
-		}
-		logDir = filepath.Join(homeDir, ".local", "share", "eget", "logs")
+		return filepath.Join(localAppData, "eget"), nil
 	}
-	
-	return logDir, nil
+	// Unix-like systems: use ~/.local/share/eget
+	homeDir, err := home.Home()
+	if err != nil {
+		return "", fmt.Errorf("could not determine home directory: %w", err)
+	}
+	return filepath.Join(homeDir, ".local", "share", "eget"), nil
 }
 
 // GetLogFilePath returns the full path to the log file
@@ -65,37 +80,138 @@ func ensureLogDir() error {
 	return os.MkdirAll(logDir, 0755)
 }
 
-// LogOperation logs a binary operation to the log file
-func LogOperation(repo, path, action string) error {
-	// Ensure log directory exists
+// A LogError is returned when the log cannot be written, most often because
+// it belongs to another user on a shared install.
+type LogError struct {
+	Path string
+	Err  error
+}
+
+func (e *LogError) Error() string {
+	return fmt.Sprintf("cannot write the log %s: %v", e.Path, e.Err)
+}
+
+func (e *LogError) Unwrap() error {
+	return e.Err
+}
+
+// CheckLogWritable returns a *LogError if the log, or the directory it would
+// be created in, cannot be written by this user. Operations that change
+// files call it first, so a read-only log does not leave them untracked.
+func CheckLogWritable() error {
+	logFile, err := GetLogFilePath()
+	if err != nil {
+		return err
+	}
 	if err := ensureLogDir(); err != nil {
-		return fmt.Errorf("failed to create log directory: %w", err)
+		return &LogError{Path: logFile, Err: err}
 	}
-	
+	f, err := os.OpenFile(logFile, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0644)
+	if err != nil {
+		return &LogError{Path: logFile, Err: err}
+	}
+	f.Close()
+	// WriteLogs replaces the log, which also needs the directory to be writable
+	tmp, err := os.CreateTemp(filepath.Dir(logFile), ".eget-log-check-")
+	if err != nil {
+		return &LogError{Path: logFile, Err: err}
+	}
+	tmp.Close()
+	os.Remove(tmp.Name())
+	return nil
+}
+
+// LogOperation logs a binary operation to the log file. The version is the
+// release tag that was installed and verified lists the kinds of signature
+// and provenance the asset was verified with; both may be empty. Relative
+// paths are logged as absolute paths and repos in their canonical form, so
+// every operation on a file or repo refers to it the same way.
+func LogOperation(repo, path, action, version, verified string) error {
+	if abs, err := filepath.Abs(path); err == nil && opts.Root == "" {
+		path = abs
+	}
+	repo = normalizeRepo(repo)
+
 	logFile, err := GetLogFilePath()
 	if err != nil {
 		return fmt.Errorf("failed to get log file path: %w", err)
 	}
+
+	// Ensure log directory exists
+	if err := ensureLogDir(); err != nil {
+		return &LogError{Path: logFile, Err: err}
+	}
 	
 	// Open file in append mode, create if doesn't exist
 	f, err := os.OpenFile(logFile, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0644)
 	if err != nil {
-		return fmt.Errorf("failed to open log file: %w", err)
+		return &LogError{Path: logFile, Err: err}
 	}
 	defer f.Close()
 	
-	// Format: timestamp\trepo\tpath\taction
-	timestamp := time.Now().UTC().Format(time.RFC3339)
-	logLine := fmt.Sprintf("%s\t%s\t%s\t%s\n", timestamp, repo, path, action)
+	logLine := FormatLogEntry(LogEntry{
+		Timestamp: time.Now().UTC(),
+		Repo:      repo,
+		Path:      path,
+		Action:    action,
+		Version:   version,
+		Verified:  verified,
+	}) + "\n"
 	
 	_, err = f.WriteString(logLine)
 	if err != nil {
-		return fmt.Errorf("failed to write to log file: %w", err)
+		return &LogError{Path: logFile, Err: err}
+	}
+	
+	return nil
+}
+
+// WriteLogs replaces the contents of the log file with the given entries
+func WriteLogs(entries []LogEntry) error {
+	logFile, err := GetLogFilePath()
+	if err != nil {
+		return fmt.Errorf("failed to get log file path: %w", err)
+	}
+	if err := ensureLogDir(); err != nil {
+		return &LogError{Path: logFile, Err: err}
+	}
+	
+	var b strings.Builder
+	for _, entry := range entries {
+		b.WriteString(FormatLogEntry(entry))
+		b.WriteString("\n")
+	}
+	
+	// write to a temporary file first so the log is never left half-written
+	tmp := logFile + ".tmp"
+	if err := os.WriteFile(tmp, []byte(b.String()), 0644); err != nil {
+		return &LogError{Path: logFile, Err: err}
+	}
+	if err := os.Rename(tmp, logFile); err != nil {
+		os.Remove(tmp)
+		return &LogError{Path: logFile, Err: err}
 	}
+	// the new log may reuse the inode of the old one, which ReadLogs would
+	// take for the same file
+	logCache.Lock()
+	logCache.file, logCache.offset, logCache.entries = nil, 0, nil
+	logCache.Unlock()
 	
 	return nil
 }
 
+// logCache holds the entries ReadLogs parsed from the log. As the log is only
+// appended to, reading it again, as commands such as --doctor do many times,
+// only parses the lines added since. The cache is dropped by WriteLogs, and a
+// log that was replaced by another process or that shrank is parsed again
+// from the start.
+var logCache struct {
+	sync.Mutex
+	file    os.FileInfo
+	offset  int64 // bytes of the log parsed into entries, up to a newline
+	entries []LogEntry
+}
+
 // ReadLogs reads all log entries from the log file
 func ReadLogs() ([]LogEntry, error) {
 	logFile, err := GetLogFilePath()
@@ -103,16 +219,52 @@ func ReadLogs() ([]LogEntry, error) {
 		return nil, err
 	}
 	
-	data, err := os.ReadFile(logFile)
+	f, err := os.Open(logFile)
 	if err != nil {
 		if os.IsNotExist(err) {
 			return []LogEntry{}, nil
 		}
 		return nil, fmt.Errorf("failed to read log file: %w", err)
 	}
-	
+	defer f.Close()
+	fi, err := f.Stat()
+	if err != nil {
+		return nil, fmt.Errorf("failed to read log file: %w", err)
+	}
+
+	logCache.Lock()
+	defer logCache.Unlock()
+	if logCache.file == nil || !os.SameFile(logCache.file, fi) || fi.Size() < logCache.offset {
+		logCache.offset, logCache.entries = 0, nil
+	}
+	logCache.file = fi
+
+	if _, err := f.Seek(logCache.offset, io.SeekStart); err != nil {
+		return nil, fmt.Errorf("failed to read log file: %w", err)
+	}
+	data, err := io.ReadAll(f)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read log file: %w", err)
+	}
+	// a line that is still being written is parsed on the next read
+	if end := bytes.LastIndexByte(data, '\n'); end >= 0 {
+		entries, _ := parseLogs(data[:end+1])
+		logCache.entries = append(logCache.entries, entries...)
+		logCache.offset += int64(end + 1)
+	}
+	if rest := data[bytes.LastIndexByte(data, '\n')+1:]; len(rest) > 0 {
+		entries, _ := parseLogs(rest)
+		return append(append([]LogEntry{}, logCache.entries...), entries...), nil
+	}
+	return append([]LogEntry{}, logCache.entries...), nil
+}
+
+// parseLogs parses the contents of a log file, returning the valid entries
+// and the number of malformed lines that were skipped
+func parseLogs(data []byte) ([]LogEntry, int) {
 	lines := strings.Split(string(data), "\n")
 	entries := make([]LogEntry, 0, len(lines))
+	malformed := 0
 	
 	for _, line := range lines {
 		line = strings.TrimSpace(line)
@@ -120,34 +272,69 @@ func ReadLogs() ([]LogEntry, error) {
 			continue
 		}
 		
+		// the version and verified columns were added later and are optional
 		parts := strings.Split(line, "\t")
-		if len(parts) != 4 {
+		if len(parts) < 4 || len(parts) > 6 {
+			malformed++
 			continue // skip malformed lines
 		}
 		
 		timestamp, err := time.Parse(time.RFC3339, parts[0])
 		if err != nil {
+			malformed++
 			continue // skip lines with invalid timestamps
 		}
 		
-		entries = append(entries, LogEntry{
+		entry := LogEntry{
 			Timestamp: timestamp,
-			Repo:      parts[1],
+			Repo:      normalizeRepo(parts[1]),
 			Path:      parts[2],
 			Action:    parts[3],
-		})
+		}
+		if len(parts) >= 5 {
+			entry.Version = parts[4]
+		}
+		if len(parts) == 6 {
+			entry.Verified = parts[5]
+		}
+		entries = append(entries, entry)
 	}
 	
-	return entries, nil
+	return entries, malformed
+}
+
+// countMalformed returns the number of malformed lines in the log.
+func countMalformed() (int, error) {
+	logFile, err := GetLogFilePath()
+	if err != nil {
+		return 0, err
+	}
+	data, err := os.ReadFile(logFile)
+	if os.IsNotExist(err) {
+		return 0, nil
+	} else if err != nil {
+		return 0, fmt.Errorf("failed to read log file: %w", err)
+	}
+	_, malformed := parseLogs(data)
+	return malformed, nil
 }
 
-// FormatLogEntry formats a log entry for display
+// FormatLogEntry formats a log entry as a line of the log file, without the
+// trailing newline.
+// Format: timestamp\trepo\tpath\taction[\tversion[\tverified]]
 func FormatLogEntry(entry LogEntry) string {
-	return fmt.Sprintf("%s\t%s\t%s\t%s",
+	line := fmt.Sprintf("%s\t%s\t%s\t%s",
 		entry.Timestamp.Format(time.RFC3339),
 		entry.Repo,
 		entry.Path,
 		entry.Action)
+	if entry.Version != "" || entry.Verified != "" {
+		line += "\t" + entry.Version
+	}
+	if entry.Verified != "" {
+		line += "\t" + entry.Verified
+	}
+	return line
 }
 
 // PrintLogs prints all log entries
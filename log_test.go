@@ -0,0 +1,105 @@
+package main
+
+import (
+	"testing"
+)
+
+func TestLogRoundTrip(t *testing.T) {
+	tests := []struct {
+		name string
+		line string
+	}{
+		{"four columns", "2024-01-02T03:04:05Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled"},
+		{"five columns", "2024-01-02T03:04:05Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\tv1.3.3"},
+		{"six columns", "2024-01-02T03:04:05Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\tv1.3.3\tsha256,attestation"},
+		{"verified without version", "2024-01-02T03:04:05Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\t\tminisign"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			entries, malformed := parseLogs([]byte(tt.line + "\n"))
+			if len(entries) != 1 || malformed != 0 {
+				t.Fatalf("parseLogs() = %d entries, %d malformed, want 1, 0", len(entries), malformed)
+			}
+			if got := FormatLogEntry(entries[0]); got != tt.line {
+				t.Errorf("FormatLogEntry() = %q, want %q", got, tt.line)
+			}
+		})
+	}
+}
+
+func TestParseLogs(t *testing.T) {
+	tests := []struct {
+		name      string
+		data      string
+		repos     []string
+		malformed int
+	}{
+		{"empty", "", []string{}, 0},
+		{"blank lines", "\n\n  \n", []string{}, 0},
+		{"repo normalized", "2024-01-02T03:04:05Z\thttps://github.com/zyedidia/eget.git\t/usr/local/bin/eget\tinstalled\n", []string{"zyedidia/eget"}, 0},
+		{"too few columns", "2024-01-02T03:04:05Z\tzyedidia/eget\t/usr/local/bin/eget\n", []string{}, 1},
+		{"too many columns", "2024-01-02T03:04:05Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\tv1\tsha256\textra\n", []string{}, 1},
+		{"invalid timestamp", "yesterday\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\n", []string{}, 1},
+		{
+			"valid lines kept",
+			"garbage\n2024-01-02T03:04:05Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\n2024-01-03T03:04:05Z\tBurntSushi/ripgrep\t/usr/local/bin/rg\tinstalled\t14.1.0",
+			[]string{"zyedidia/eget", "BurntSushi/ripgrep"},
+			1,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			entries, malformed := parseLogs([]byte(tt.data))
+			if malformed != tt.malformed {
+				t.Errorf("parseLogs() malformed = %d, want %d", malformed, tt.malformed)
+			}
+			if len(entries) != len(tt.repos) {
+				t.Fatalf("parseLogs() = %d entries, want %d", len(entries), len(tt.repos))
+			}
+			for i, e := range entries {
+				if e.Repo != tt.repos[i] {
+					t.Errorf("entry %d repo = %q, want %q", i, e.Repo, tt.repos[i])
+				}
+			}
+		})
+	}
+}
+
+func TestReadLogsAfterWriteLogs(t *testing.T) {
+	tests := []struct {
+		name    string
+		log     string
+		written []string
+	}{
+		{"replaced", "2024-01-01T00:00:00Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\n", []string{"BurntSushi/ripgrep", "sharkdp/fd"}},
+		{"emptied", "2024-01-01T00:00:00Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\n", []string{}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			useLog(t, tt.log)
+			if _, err := ReadLogs(); err != nil {
+				t.Fatal(err)
+			}
+
+			entries := []LogEntry{}
+			for _, repo := range tt.written {
+				entries = append(entries, LogEntry{Repo: repo, Path: "/usr/local/bin/" + repo, Action: StatusInstalled})
+			}
+			if err := WriteLogs(entries); err != nil {
+				t.Fatal(err)
+			}
+			got, err := ReadLogs()
+			if err != nil {
+				t.Fatal(err)
+			}
+			if len(got) != len(tt.written) {
+				t.Fatalf("ReadLogs() = %d entries, want %d", len(got), len(tt.written))
+			}
+			for i, e := range got {
+				if e.Repo != tt.written[i] {
+					t.Errorf("entry %d repo = %q, want %q", i, e.Repo, tt.written[i])
+				}
+			}
+		})
+	}
+}
@@ -0,0 +1,314 @@
+package main
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/zyedidia/eget/home"
+)
+
+// Kinds of difference between a manifest and the installed tools.
+const (
+	DiffMissing = "missing" // in the manifest but not installed
+	DiffExtra   = "extra"   // installed but not in the manifest
+	DiffVersion = "version" // installed with a different version than pinned
+)
+
+// ReadTargets reads a list of targets to install from path, or from stdin if
+// path is "-". Each line holds a target optionally followed by options for
+// it; blank lines and lines starting with '#' are ignored. The returned
+// argument lists have the target last.
+func ReadTargets(path string) ([][]string, error) {
+	var r io.Reader = os.Stdin
+	if path != "-" {
+		f, err := os.Open(path)
+		if err != nil {
+			return nil, err
+		}
+		defer f.Close()
+		r = f
+	}
+
+	runs := [][]string{}
+	scanner := bufio.NewScanner(r)
+	for scanner.Scan() {
+		fields := strings.Fields(scanner.Text())
+		if len(fields) == 0 || strings.HasPrefix(fields[0], "#") {
+			continue
+		}
+		runs = append(runs, append(fields[1:], fields[0]))
+	}
+	return runs, scanner.Err()
+}
+
+// A Difference between the repositories of a manifest (a config file) and the
+// tools that are installed.
+type Difference struct {
+	Repo      string `json:"repo"`
+	Kind      string `json:"kind"`
+	Installed string `json:"installed,omitempty"`
+	Wanted    string `json:"wanted,omitempty"`
+}
+
+// LoadManifest loads a config file to be used as a manifest of the tools that
+// should be installed.
+func LoadManifest(path string) (*Config, error) {
+	manifest, err := LoadConfigurationFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", path, err)
+	}
+	delete(manifest.Repositories, "global")
+	return &manifest, nil
+}
+
+// DiffManifest compares the repositories of manifest with the installed
+// tools. Versions are only compared for repositories pinned to a tag, using
+// the same partial matching as --tag.
+func DiffManifest(manifest *Config, tools []Tool) []Difference {
+	installed := make(map[string]Tool)
+	for _, t := range tools {
+		if t.Status == StatusInstalled {
+			installed[repoKey(t.Repo)] = t
+		}
+	}
+
+	diffs := []Difference{}
+	wanted := make(map[string]bool)
+	for name, repo := range manifest.Repositories {
+		key := repoKey(name)
+		wanted[key] = true
+		t, ok := installed[key]
+		if !ok {
+			diffs = append(diffs, Difference{Repo: name, Kind: DiffMissing, Wanted: repo.Tag})
+		} else if repo.Tag != "" && !strings.Contains(t.Version, repo.Tag) {
+			diffs = append(diffs, Difference{Repo: name, Kind: DiffVersion, Installed: t.Version, Wanted: repo.Tag})
+		}
+	}
+	for key, t := range installed {
+		if !wanted[key] {
+			diffs = append(diffs, Difference{Repo: t.Repo, Kind: DiffExtra, Installed: t.Version})
+		}
+	}
+
+	sort.Slice(diffs, func(i, j int) bool {
+		return diffs[i].Repo < diffs[j].Repo
+	})
+	return diffs
+}
+
+// ExportManifest writes the installed tools to w as a manifest: a config file
+// with one section per repo, meant to be committed to a dotfiles repository
+// and installed elsewhere with --apply. Sections are sorted by repo and
+// install directories under the home directory are written relative to '~'
+// so the manifest works on other machines. If pin is true, each repo is
+// pinned to its installed release tag. The header records date as the day
+// the manifest was generated.
+func ExportManifest(w io.Writer, tools []Tool, pin bool, date time.Time) error {
+	fmt.Fprintf(w, "# eget manifest, generated by `eget --export` on %s.\n", date.Format("2006-01-02"))
+	fmt.Fprintln(w, "# Install or update everything listed here with `eget --apply FILE`.")
+	return writeManifestSections(w, manifestTools(tools), pin)
+}
+
+// manifestTools returns the installed tools that can be listed in a manifest,
+// one per repo, sorted by repo.
+func manifestTools(tools []Tool) []Tool {
+	installed := []Tool{}
+	seen := make(map[string]bool)
+	for _, t := range tools {
+		key := repoKey(t.Repo)
+		if t.Status != StatusInstalled || t.Source == SourceFile || seen[key] {
+			continue
+		}
+		seen[key] = true
+		installed = append(installed, t)
+	}
+	sort.Slice(installed, func(i, j int) bool {
+		return repoKey(installed[i].Repo) < repoKey(installed[j].Repo)
+	})
+	return installed
+}
+
+// writeManifestSections writes one manifest section per tool to w.
+func writeManifestSections(w io.Writer, tools []Tool, pin bool) error {
+	homeDir, _ := home.Home()
+	for _, t := range tools {
+		dir, err := filepath.Abs(filepath.Dir(t.Path))
+		if err != nil {
+			return err
+		}
+		if homeDir != "" {
+			if rel, err := filepath.Rel(homeDir, dir); err == nil && !strings.HasPrefix(rel, "..") {
+				dir = filepath.ToSlash(filepath.Join("~", rel))
+			}
+		}
+
+		fmt.Fprintf(w, "\n# %s\n", t.Name)
+		fmt.Fprintf(w, "[%s]\n", strconv.Quote(t.Repo))
+		fmt.Fprintf(w, "    target = %s\n", strconv.Quote(dir))
+		if pin && t.Version != "" {
+			fmt.Fprintf(w, "    tag = %s\n", strconv.Quote(t.Version))
+		}
+	}
+	return nil
+}
+
+// FetchManifest downloads a manifest published at url into the cache
+// directory and returns the path of the local copy.
+func FetchManifest(url string) (string, error) {
+	dir, err := GetCacheDir()
+	if err != nil {
+		return "", err
+	}
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return "", err
+	}
+	path := filepath.Join(dir, "remote-manifest.toml")
+	if err := downloadTo(url, path); err != nil {
+		return "", fmt.Errorf("manifest: %w", err)
+	}
+	return path, nil
+}
+
+// ApplyTargets returns the targets to install so the tools match the
+// manifest: the repos that are not installed and those installed with a
+// different version than pinned, each with its options from the manifest.
+func ApplyTargets(manifest *Config, tools []Tool) [][]string {
+	runs := [][]string{}
+	for _, d := range DiffManifest(manifest, tools) {
+		if d.Kind == DiffMissing || d.Kind == DiffVersion {
+			runs = append(runs, append(manifestArgs(manifest, d.Repo), d.Repo))
+		}
+	}
+	return runs
+}
+
+// manifestOptions returns the options of repo in manifest, with the settings
+// of the manifest's [global] section that apply to repositories filling in
+// the ones the section of repo does not set.
+func manifestOptions(manifest *Config, repo string) ConfigRepository {
+	r := manifest.Repositories[repo]
+	meta := manifest.Meta.MetaData
+	if meta == nil {
+		return r
+	}
+	defined := func(key string) bool {
+		return meta.IsDefined(repo, key)
+	}
+	g := manifest.Global
+	if !defined("all") {
+		r.All = g.All
+	}
+	if !defined("download_only") {
+		r.DownloadOnly = g.DownloadOnly
+	}
+	if !defined("download_source") {
+		r.Source = g.Source
+	}
+	if !defined("file") {
+		r.File = g.File
+	}
+	if !defined("system") {
+		r.System = g.System
+	}
+	if !defined("target") {
+		r.Target = g.Target
+	}
+	if !defined("upgrade_only") {
+		r.UpgradeOnly = g.UpgradeOnly
+	}
+	if !defined("verify_provenance") {
+		r.Provenance = g.Provenance
+	}
+	return r
+}
+
+// manifestArgs returns the command line options that install repo with its
+// options in manifest. Installs from a manifest run with the user's own
+// config file, so settings such as github_token and ca_file keep applying,
+// and the manifest's options override the user's for the repo. The settings
+// that weaken or choose how a release is verified, disable_ssl and
+// public_key, are taken from the user's config file only.
+func manifestArgs(manifest *Config, repo string) []string {
+	r := manifestOptions(manifest, repo)
+	args := []string{}
+	if r.Target != "" {
+		target, err := home.Expand(r.Target)
+		if err != nil {
+			target = r.Target
+		}
+		args = append(args, "--to", target)
+	}
+	if r.Tag != "" {
+		args = append(args, "--tag", r.Tag)
+	}
+	if r.System != "" {
+		args = append(args, "--system", r.System)
+	}
+	for _, a := range r.AssetFilters {
+		args = append(args, "--asset", a)
+	}
+	if r.File != "" {
+		args = append(args, "--file", r.File)
+	}
+	if r.Verify != "" {
+		args = append(args, "--verify-sha256", r.Verify)
+	}
+	flags := []struct {
+		set  bool
+		flag string
+	}{
+		{r.All, "--all"},
+		{r.DownloadOnly, "--download-only"},
+		{r.Source, "--source"},
+		{r.UpgradeOnly, "--upgrade-only"},
+		{r.Provenance, "--verify-provenance"},
+	}
+	for _, f := range flags {
+		if f.set {
+			args = append(args, f.flag)
+		}
+	}
+	return args
+}
+
+// PrintDifferences writes diffs to w in the given format: "table" (the
+// default), "json" or "porcelain".
+func PrintDifferences(w io.Writer, diffs []Difference, format string) error {
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2)
+		for _, d := range diffs {
+			switch d.Kind {
+			case DiffMissing:
+				fmt.Fprintf(tw, "+ %s\tnot installed\n", d.Repo)
+			case DiffExtra:
+				fmt.Fprintf(tw, "- %s\tnot in manifest\n", d.Repo)
+			case DiffVersion:
+				installed := d.Installed
+				if installed == "" {
+					installed = "unknown version"
+				}
+				fmt.Fprintf(tw, "~ %s\t%s, manifest wants %s\n", d.Repo, installed, d.Wanted)
+			}
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(diffs)
+	case "porcelain":
+		for _, d := range diffs {
+			writePorcelain(w, d.Kind, d.Repo, d.Installed, d.Wanted)
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
@@ -0,0 +1,114 @@
+package main
+
+import (
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestDiffManifest(t *testing.T) {
+	tools := []Tool{
+		{Name: "eget", Repo: "zyedidia/eget", Status: StatusInstalled, Version: "v1.3.3"},
+		{Name: "rg", Repo: "BurntSushi/ripgrep", Status: StatusInstalled, Version: "14.1.0"},
+		{Name: "fd", Repo: "sharkdp/fd", Status: StatusRemoved, Version: "v9.0.0"},
+	}
+
+	tests := []struct {
+		name  string
+		repos map[string]ConfigRepository
+		want  []Difference
+	}{
+		{
+			"in sync",
+			map[string]ConfigRepository{"zyedidia/eget": {}, "BurntSushi/ripgrep": {}},
+			[]Difference{},
+		},
+		{
+			"other spellings of installed repos",
+			map[string]ConfigRepository{"https://github.com/Zyedidia/Eget": {}, "burntsushi/ripgrep": {}},
+			[]Difference{},
+		},
+		{
+			"partial tag matches",
+			map[string]ConfigRepository{"zyedidia/eget": {Tag: "v1.3"}, "BurntSushi/ripgrep": {Tag: "14"}},
+			[]Difference{},
+		},
+		{
+			"missing",
+			map[string]ConfigRepository{"zyedidia/eget": {}, "BurntSushi/ripgrep": {}, "sharkdp/fd": {Tag: "v9"}},
+			[]Difference{{Repo: "sharkdp/fd", Kind: DiffMissing, Wanted: "v9"}},
+		},
+		{
+			"different version",
+			map[string]ConfigRepository{"zyedidia/eget": {Tag: "v1.4"}, "BurntSushi/ripgrep": {}},
+			[]Difference{{Repo: "zyedidia/eget", Kind: DiffVersion, Installed: "v1.3.3", Wanted: "v1.4"}},
+		},
+		{
+			"extra",
+			map[string]ConfigRepository{"zyedidia/eget": {}},
+			[]Difference{{Repo: "BurntSushi/ripgrep", Kind: DiffExtra, Installed: "14.1.0"}},
+		},
+		{
+			"sorted by repo",
+			map[string]ConfigRepository{"zyedidia/eget": {Tag: "v2"}, "sharkdp/fd": {}},
+			[]Difference{
+				{Repo: "BurntSushi/ripgrep", Kind: DiffExtra, Installed: "14.1.0"},
+				{Repo: "sharkdp/fd", Kind: DiffMissing},
+				{Repo: "zyedidia/eget", Kind: DiffVersion, Installed: "v1.3.3", Wanted: "v2"},
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			manifest := &Config{Repositories: tt.repos}
+			if got := DiffManifest(manifest, tools); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("DiffManifest() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestExportManifest(t *testing.T) {
+	tools := []Tool{
+		{Name: "rg", Repo: "BurntSushi/ripgrep", Source: SourceRelease, Path: "/opt/eget/bin/rg", Status: StatusInstalled, Version: "14.1.0"},
+		{Name: "eget", Repo: "zyedidia/eget", Source: SourceRelease, Path: "/opt/eget/bin/eget", Status: StatusInstalled, Version: "v1.3.3"},
+		{Name: "rg", Repo: "burntsushi/ripgrep", Source: SourceRelease, Path: "/usr/bin/rg", Status: StatusInstalled, Version: "14.1.0"},
+		{Name: "fd", Repo: "sharkdp/fd", Source: SourceRelease, Path: "/opt/eget/bin/fd", Status: StatusRemoved, Version: "v9.0.0"},
+		{Name: "tool", Repo: "/tmp/tool", Source: SourceFile, Path: "/opt/eget/bin/tool", Status: StatusInstalled},
+	}
+	header := "# eget manifest, generated by `eget --export` on 2024-05-06.\n" +
+		"# Install or update everything listed here with `eget --apply FILE`.\n"
+
+	tests := []struct {
+		name string
+		pin  bool
+		want string
+	}{
+		{
+			"unpinned",
+			false,
+			header +
+				"\n# rg\n[\"BurntSushi/ripgrep\"]\n    target = \"/opt/eget/bin\"\n" +
+				"\n# eget\n[\"zyedidia/eget\"]\n    target = \"/opt/eget/bin\"\n",
+		},
+		{
+			"pinned",
+			true,
+			header +
+				"\n# rg\n[\"BurntSushi/ripgrep\"]\n    target = \"/opt/eget/bin\"\n    tag = \"14.1.0\"\n" +
+				"\n# eget\n[\"zyedidia/eget\"]\n    target = \"/opt/eget/bin\"\n    tag = \"v1.3.3\"\n",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var b strings.Builder
+			if err := ExportManifest(&b, tools, tt.pin, time.Date(2024, 5, 6, 12, 0, 0, 0, time.UTC)); err != nil {
+				t.Fatalf("ExportManifest() error = %v", err)
+			}
+			if got := b.String(); got != tt.want {
+				t.Errorf("ExportManifest() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,106 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"runtime"
+	"strings"
+)
+
+// A DownloadError lists the targets of a batch download that failed, and
+// the number that were skipped because the batch was cancelled.
+type DownloadError struct {
+	Errs    []error
+	Skipped int
+}
+
+func (e *DownloadError) Error() string {
+	if e.Skipped > 0 {
+		return fmt.Sprintf("cancelled with %d targets left (errors: %v)", e.Skipped, e.Errs)
+	}
+	return fmt.Sprintf("one or more errors occurred while downloading: %v", e.Errs)
+}
+
+// Is makes a cancelled batch match ErrCancelled.
+func (e *DownloadError) Is(target error) bool {
+	return target == ErrCancelled && e.Skipped > 0
+}
+
+// failedCount returns the number of targets that failed or were skipped
+// recorded in err, which is the result of downloadTargets.
+func failedCount(err error) int {
+	var de *DownloadError
+	if errors.As(err, &de) {
+		return len(de.Errs) + de.Skipped
+	}
+	if err != nil {
+		return 1
+	}
+	return 0
+}
+
+// Notify shows a desktop notification with notify-send on Linux and the BSDs,
+// osascript on macOS and PowerShell on Windows.
+func Notify(title, message string) error {
+	var cmd *exec.Cmd
+	switch runtime.GOOS {
+	case "darwin":
+		script := fmt.Sprintf("display notification %s with title %s", appleScriptString(message), appleScriptString(title))
+		cmd = exec.Command("osascript", "-e", script)
+	case "windows":
+		script := fmt.Sprintf(`Add-Type -AssemblyName System.Windows.Forms
+$n = New-Object System.Windows.Forms.NotifyIcon
+$n.Icon = [System.Drawing.SystemIcons]::Information
+$n.Visible = $true
+$n.ShowBalloonTip(10000, %s, %s, 'Info')
+Start-Sleep -Seconds 10
+$n.Dispose()`, powershellString(title), powershellString(message))
+		cmd = exec.Command("powershell", "-NoProfile", "-NonInteractive", "-Command", script)
+	default:
+		cmd = exec.Command("notify-send", "--app-name=eget", title, message)
+	}
+	if out, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("%s: %w: %s", cmd.Path, err, strings.TrimSpace(string(out)))
+	}
+	return nil
+}
+
+func appleScriptString(s string) string {
+	return `"` + strings.NewReplacer(`\`, `\\`, `"`, `\"`).Replace(s) + `"`
+}
+
+func powershellString(s string) string {
+	return "'" + strings.ReplaceAll(s, "'", "''") + "'"
+}
+
+// updateSummary describes the outcome of --update-all or --outdated for a
+// notification, e.g. "3 updated, 1 failed".
+func updateSummary(updated, failed, available int) string {
+	parts := []string{}
+	if updated > 0 {
+		parts = append(parts, fmt.Sprintf("%d updated", updated))
+	}
+	if failed > 0 {
+		parts = append(parts, fmt.Sprintf("%d failed", failed))
+	}
+	if available > 0 {
+		parts = append(parts, fmt.Sprintf("%d new updates available", available))
+	}
+	if len(parts) == 0 {
+		return "All tracked tools are up to date"
+	}
+	return strings.Join(parts, ", ")
+}
+
+// notifyUpdates sends the summary as a desktop notification if --notify is
+// set, and warns if that fails.
+func notifyUpdates(updated, failed, available int) {
+	if !opts.Notify {
+		return
+	}
+	if err := Notify("eget", updateSummary(updated, failed, available)); err != nil {
+		fmt.Fprintln(os.Stderr, "warning: cannot show notification:", err)
+	}
+}
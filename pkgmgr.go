@@ -0,0 +1,145 @@
+package main
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strings"
+
+	"github.com/zyedidia/eget/home"
+)
+
+// lookPathAll returns every executable named name in the directories of
+// $PATH, in the order the shell would find them, like `which -a`.
+func lookPathAll(name string) []string {
+	names := []string{name}
+	if runtime.GOOS == "windows" && filepath.Ext(name) == "" {
+		names = append(names, name+".exe")
+	}
+
+	found := []string{}
+	seen := make(map[string]bool)
+	for _, dir := range filepath.SplitList(os.Getenv("PATH")) {
+		if dir == "" {
+			continue
+		}
+		for _, n := range names {
+			p := filepath.Join(dir, n)
+			fi, err := os.Stat(p)
+			if err != nil || !isExecutable(fi) {
+				continue
+			}
+			abs, err := filepath.Abs(p)
+			if err != nil || seen[abs] {
+				continue
+			}
+			seen[abs] = true
+			found = append(found, p)
+		}
+	}
+	return found
+}
+
+// packageOwner returns the name of the system package manager that installed
+// the file at path ("brew", "snap", "apt", "dnf" or "pacman"), or "" if none
+// of them claims it.
+func packageOwner(path string) string {
+	resolved, err := filepath.EvalSymlinks(path)
+	if err != nil {
+		resolved = path
+	}
+	if strings.Contains(resolved, "/Cellar/") || strings.Contains(resolved, "/homebrew/") || strings.Contains(resolved, "/linuxbrew/") {
+		return "brew"
+	}
+	if strings.HasPrefix(path, "/snap/") || strings.HasPrefix(resolved, "/snap/") {
+		return "snap"
+	}
+	if runtime.GOOS != "linux" {
+		return ""
+	}
+
+	// each package manager is asked whether it owns the file, and only the
+	// ones that are installed can answer
+	queries := []struct {
+		manager string
+		args    []string
+	}{
+		{"apt", []string{"dpkg", "-S", resolved}},
+		{"dnf", []string{"rpm", "-qf", resolved}},
+		{"pacman", []string{"pacman", "-Qo", resolved}},
+	}
+	for _, q := range queries {
+		if _, err := exec.LookPath(q.args[0]); err != nil {
+			continue
+		}
+		if exec.Command(q.args[0], q.args[1:]...).Run() == nil {
+			return q.manager
+		}
+	}
+	return ""
+}
+
+// shadowedBy returns the executable that the shell runs instead of t because
+// it comes earlier in PATH, or "" if t is not shadowed. A tool whose directory
+// is not on PATH at all is not considered shadowed.
+func shadowedBy(t Tool) string {
+	found := lookPathAll(t.Name)
+	for i, p := range found {
+		if sameFile(p, t.Path) {
+			if i > 0 {
+				return found[0]
+			}
+			return ""
+		}
+	}
+	return ""
+}
+
+// shimDirs returns the directories in which the mise and asdf version managers
+// put their shims, keyed by the name of the version manager.
+func shimDirs() map[string]string {
+	homeDir, _ := home.Home()
+	dirs := make(map[string]string)
+
+	mise := os.Getenv("MISE_DATA_DIR")
+	if mise == "" {
+		if xdg := os.Getenv("XDG_DATA_HOME"); xdg != "" {
+			mise = filepath.Join(xdg, "mise")
+		} else if homeDir != "" {
+			mise = filepath.Join(homeDir, ".local", "share", "mise")
+		}
+	}
+	if mise != "" {
+		dirs["mise"] = filepath.Join(mise, "shims")
+	}
+
+	asdf := os.Getenv("ASDF_DATA_DIR")
+	if asdf == "" && homeDir != "" {
+		asdf = filepath.Join(homeDir, ".asdf")
+	}
+	if asdf != "" {
+		dirs["asdf"] = filepath.Join(asdf, "shims")
+	}
+	return dirs
+}
+
+// versionManager returns the version manager ("mise" or "asdf") that also
+// provides a command named name through a shim, and the path of the shim. It
+// returns "" if neither does.
+func versionManager(name string) (string, string) {
+	for _, manager := range []string{"mise", "asdf"} {
+		dir, ok := shimDirs()[manager]
+		if !ok {
+			continue
+		}
+		shim := filepath.Join(dir, name)
+		if runtime.GOOS == "windows" && filepath.Ext(name) == "" {
+			shim += ".exe"
+		}
+		if fi, err := os.Stat(shim); err == nil && isExecutable(fi) {
+			return manager, shim
+		}
+	}
+	return "", ""
+}
@@ -0,0 +1,48 @@
+package main
+
+import (
+	"runtime"
+	"sync"
+)
+
+// defaultMaxRequests is the number of network requests, such as checks for
+// the latest release of tracked binaries, that are made at the same time
+// unless max_requests is set.
+const defaultMaxRequests = 8
+
+// parallel calls f(i) for every i in [0, n) on a pool of workers and waits
+// for all of them. It is used for per-tool filesystem work such as stat,
+// hashing and reading binary headers, which mostly waits on the disk, so
+// there are more workers than CPUs. f must only write to the i-th element of
+// whatever it fills in.
+func parallel(n int, f func(i int)) {
+	parallelLimit(n, 4*runtime.NumCPU(), f)
+}
+
+// parallelLimit is like parallel, with at most workers calls to f running at
+// the same time.
+func parallelLimit(n, workers int, f func(i int)) {
+	if workers < 1 {
+		workers = 1
+	}
+	if workers > n {
+		workers = n
+	}
+	next := make(chan int)
+	var wg sync.WaitGroup
+	for w := 0; w < workers; w++ {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			defer recoverCrash()
+			for i := range next {
+				f(i)
+			}
+		}()
+	}
+	for i := 0; i < n; i++ {
+		next <- i
+	}
+	close(next)
+	wg.Wait()
+}
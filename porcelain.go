@@ -0,0 +1,38 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// The porcelain format is meant for scripts and stays the same across
+// versions: one record per line, fields separated by tabs, with backslashes,
+// tabs and newlines inside fields escaped as \\, \t and \n. New fields are
+// only ever appended at the end of a record. The layout of each listing is
+// documented in the man page.
+
+var porcelainEscaper = strings.NewReplacer(`\`, `\\`, "\t", `\t`, "\n", `\n`, "\r", `\r`)
+
+// writePorcelain writes a single porcelain record to w.
+func writePorcelain(w io.Writer, fields ...string) {
+	for i := range fields {
+		fields[i] = porcelainEscaper.Replace(fields[i])
+	}
+	fmt.Fprintln(w, strings.Join(fields, "\t"))
+}
+
+// porcelainTime formats t as an RFC 3339 UTC timestamp.
+func porcelainTime(t time.Time) string {
+	return t.UTC().Format(time.RFC3339)
+}
+
+func porcelainTool(w io.Writer, t Tool, extra ...string) {
+	fields := []string{
+		t.Name, t.Repo, t.Path, t.Status, strconv.FormatInt(t.Size, 10),
+		porcelainTime(t.Installed), porcelainTime(t.Updated), t.Version,
+	}
+	writePorcelain(w, append(fields, extra...)...)
+}
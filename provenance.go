@@ -0,0 +1,68 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path"
+	"path/filepath"
+	"strings"
+)
+
+// Kinds of provenance eget can verify.
+const (
+	ProvAttestation = "attestation" // a GitHub build attestation, checked with gh
+	ProvSigstore    = "sigstore"    // a sigstore bundle, checked with cosign
+)
+
+// githubActionsIssuer is the OIDC issuer of certificates obtained by GitHub
+// Actions workflows.
+const githubActionsIssuer = "https://token.actions.githubusercontent.com"
+
+// sigstoreBundleAsset searches for a sigstore bundle for asset, as published
+// by cosign or the GitHub attest actions, and returns its URL or "".
+func sigstoreBundleAsset(asset string, assets []string) string {
+	for _, a := range assets {
+		if a == asset+".sigstore.json" || a == asset+".sigstore" || a == asset+".bundle" {
+			return a
+		}
+	}
+	return ""
+}
+
+// VerifyProvenance checks that body, the contents of the asset at assetURL,
+// was built by a GitHub Actions workflow of repo. If the release has a
+// sigstore bundle for the asset it is verified with cosign, otherwise the
+// build attestation is looked up and verified with the gh CLI. It returns the
+// kind of provenance that was verified.
+func VerifyProvenance(body []byte, assetURL string, assets []string, repo string) (string, error) {
+	dir, err := os.MkdirTemp("", "eget-provenance")
+	if err != nil {
+		return "", err
+	}
+	defer os.RemoveAll(dir)
+
+	file := filepath.Join(dir, path.Base(assetURL))
+	if err := os.WriteFile(file, body, 0644); err != nil {
+		return "", err
+	}
+
+	kind := ProvAttestation
+	args := []string{"gh", "attestation", "verify", file, "--repo", repo}
+	if bundleURL := sigstoreBundleAsset(assetURL, assets); bundleURL != "" {
+		bundle := filepath.Join(dir, path.Base(bundleURL))
+		if err := downloadTo(bundleURL, bundle); err != nil {
+			return "", fmt.Errorf("sigstore bundle: %w", err)
+		}
+		kind = ProvSigstore
+		args = []string{"cosign", "verify-blob", "--bundle", bundle,
+			"--certificate-identity-regexp", fmt.Sprintf("^https://github.com/%s/", repo),
+			"--certificate-oidc-issuer", githubActionsIssuer, file}
+	}
+
+	out, err := exec.Command(args[0], args[1:]...).CombinedOutput()
+	if err != nil {
+		return "", fmt.Errorf("%s verification failed: %w\n%s", kind, err, strings.TrimSpace(string(out)))
+	}
+	return kind, nil
+}
@@ -0,0 +1,28 @@
+package main
+
+import (
+	"os/exec"
+	"runtime"
+)
+
+// quarantineAttr is the extended attribute macOS sets on downloaded files,
+// which makes Gatekeeper refuse to run unsigned binaries.
+const quarantineAttr = "com.apple.quarantine"
+
+// hasQuarantine returns true if the file at path has the macOS quarantine
+// attribute. It is always false on other systems.
+func hasQuarantine(path string) bool {
+	if runtime.GOOS != "darwin" {
+		return false
+	}
+	return exec.Command("xattr", "-p", quarantineAttr, path).Run() == nil
+}
+
+// clearQuarantine removes the macOS quarantine attribute from the file at
+// path, if it has one.
+func clearQuarantine(path string) error {
+	if !hasQuarantine(path) {
+		return nil
+	}
+	return runQuiet(exec.Command("xattr", "-d", quarantineAttr, path))
+}
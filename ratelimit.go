@@ -0,0 +1,92 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+)
+
+// parseRate parses a download rate in bytes per second, such as "500K" or
+// "2M", like curl's --limit-rate. The suffixes K, M and G multiply by 1024,
+// 1024² and 1024³, and may be followed by a B. The empty rate is 0, which
+// means unlimited.
+func parseRate(s string) (int64, error) {
+	num := strings.TrimSuffix(strings.ToUpper(strings.TrimSpace(s)), "B")
+	if num == "" {
+		return 0, nil
+	}
+	mult := int64(1)
+	switch num[len(num)-1] {
+	case 'K':
+		mult = 1 << 10
+	case 'M':
+		mult = 1 << 20
+	case 'G':
+		mult = 1 << 30
+	}
+	if mult > 1 {
+		num = num[:len(num)-1]
+	}
+	n, err := strconv.ParseFloat(num, 64)
+	if err != nil || n < 0 {
+		return 0, fmt.Errorf("invalid rate '%s' (must be a number of bytes per second, such as 500K or 2M)", s)
+	}
+	return int64(n * float64(mult)), nil
+}
+
+// A rateLimiter spaces out reads so the bytes read through it do not exceed
+// a rate. It is shared by the downloads of one process, so the rate caps
+// their total; batches, whose targets run in separate processes, divide the
+// rate between them instead (see downloadTargets).
+type rateLimiter struct {
+	mu   sync.Mutex
+	rate int64     // bytes per second
+	next time.Time // when the bytes read so far are allowed
+}
+
+var downloadLimiter = &rateLimiter{}
+
+// limitReader returns r, limited to the --limit-rate if there is one.
+func limitReader(r io.Reader) io.Reader {
+	if opts.LimitRate <= 0 {
+		return r
+	}
+	downloadLimiter.mu.Lock()
+	downloadLimiter.rate = opts.LimitRate
+	downloadLimiter.mu.Unlock()
+	return &limitedReader{r: r, l: downloadLimiter}
+}
+
+// wait blocks until n more bytes are allowed.
+func (l *rateLimiter) wait(n int) {
+	l.mu.Lock()
+	now := time.Now()
+	if l.next.Before(now) {
+		l.next = now
+	}
+	l.next = l.next.Add(time.Duration(int64(n) * int64(time.Second) / l.rate))
+	d := l.next.Sub(now)
+	l.mu.Unlock()
+	time.Sleep(d)
+}
+
+type limitedReader struct {
+	r io.Reader
+	l *rateLimiter
+}
+
+func (r *limitedReader) Read(p []byte) (int, error) {
+	// read at most a tenth of a second's worth at a time, so the rate is
+	// smooth rather than bursts followed by long pauses
+	if max := r.l.rate / 10; max > 0 && int64(len(p)) > max {
+		p = p[:max]
+	}
+	n, err := r.r.Read(p)
+	if n > 0 {
+		r.l.wait(n)
+	}
+	return n, err
+}
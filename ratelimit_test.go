@@ -0,0 +1,37 @@
+package main
+
+import (
+	"testing"
+)
+
+func TestParseRate(t *testing.T) {
+	tests := []struct {
+		in      string
+		want    int64
+		wantErr bool
+	}{
+		{"", 0, false},
+		{"  ", 0, false},
+		{"500", 500, false},
+		{"100B", 100, false},
+		{"500K", 500 << 10, false},
+		{"1.5k", 1536, false},
+		{"2M", 2 << 20, false},
+		{"2MB", 2 << 20, false},
+		{"1G", 1 << 30, false},
+		{"K", 0, true},
+		{"fast", 0, true},
+		{"-1K", 0, true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.in, func(t *testing.T) {
+			got, err := parseRate(tt.in)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("parseRate(%q) error = %v, wantErr %v", tt.in, err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("parseRate(%q) = %d, want %d", tt.in, got, tt.want)
+			}
+		})
+	}
+}
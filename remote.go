@@ -0,0 +1,80 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"strings"
+)
+
+// remoteArgs returns args without the --host options, so they can be passed
+// on to the eget on a remote host.
+func remoteArgs(args []string) []string {
+	out := []string{}
+	for i := 0; i < len(args); i++ {
+		a := args[i]
+		if a == "--" {
+			return append(out, args[i:]...)
+		}
+		if a == "--host" {
+			i++
+			continue
+		}
+		if strings.HasPrefix(a, "--host=") {
+			continue
+		}
+		out = append(out, a)
+	}
+	return out
+}
+
+// shellQuote quotes s for a POSIX shell.
+func shellQuote(s string) string {
+	return "'" + strings.ReplaceAll(s, "'", `'\''`) + "'"
+}
+
+// RunRemote runs eget with args on each host over ssh, so the log, files and
+// config file of that host are used. Output of every host is preceded by a
+// header when there is more than one. It returns the highest exit status of
+// the remote commands.
+func RunRemote(hosts []string, args []string) (int, error) {
+	if _, err := exec.LookPath("ssh"); err != nil {
+		return ExitError, fmt.Errorf("--host requires ssh on PATH: %w", err)
+	}
+
+	quoted := make([]string, len(args))
+	for i, a := range args {
+		quoted[i] = shellQuote(a)
+	}
+	command := strings.TrimSpace("eget " + strings.Join(quoted, " "))
+
+	status := ExitOK
+	for _, host := range hosts {
+		if len(hosts) > 1 {
+			fmt.Fprintf(os.Stderr, "==> %s\n", host)
+		}
+		sshArgs := []string{}
+		if isTerminal(os.Stdin) && isTerminal(os.Stdout) {
+			sshArgs = append(sshArgs, "-t")
+		}
+		sshArgs = append(sshArgs, "--", host, command)
+
+		cmd := exec.Command("ssh", sshArgs...)
+		cmd.Stdin = os.Stdin
+		cmd.Stdout = os.Stdout
+		cmd.Stderr = os.Stderr
+		code, err := runForeground(cmd)
+		if err != nil {
+			return ExitError, err
+		}
+		// ssh exits with 255 when it cannot connect.
+		if code == 255 {
+			fmt.Fprintf(os.Stderr, "error: cannot connect to %s\n", host)
+			code = ExitError
+		}
+		if code > status {
+			status = code
+		}
+	}
+	return status, nil
+}
@@ -0,0 +1,41 @@
+package main
+
+import (
+	"io"
+	"os"
+)
+
+// backupBinary copies the file at path next to it before it is replaced by an
+// update, and returns the path of the copy. It returns "" if the file cannot
+// be copied, in which case the update goes ahead without a way back.
+func backupBinary(path string) string {
+	backup := path + ".eget-backup"
+	in, err := os.Open(path)
+	if err != nil {
+		return ""
+	}
+	defer in.Close()
+	fi, err := in.Stat()
+	if err != nil || !fi.Mode().IsRegular() {
+		return ""
+	}
+	out, err := os.OpenFile(backup, os.O_WRONLY|os.O_CREATE|os.O_TRUNC, fi.Mode().Perm())
+	if err != nil {
+		return ""
+	}
+	_, err = io.Copy(out, in)
+	if cerr := out.Close(); err == nil {
+		err = cerr
+	}
+	if err != nil {
+		os.Remove(backup)
+		return ""
+	}
+	return backup
+}
+
+// restoreBinary moves the backup made by backupBinary back to path.
+func restoreBinary(backup, path string) error {
+	os.Remove(path)
+	return os.Rename(backup, path)
+}
@@ -0,0 +1,26 @@
+package main
+
+import (
+	"fmt"
+	"path/filepath"
+)
+
+// rootPath returns the location on this system of the absolute path p inside
+// the --root directory. Relative paths and paths when no root is set are
+// returned unchanged.
+func rootPath(p string) string {
+	if opts.Root == "" || !filepath.IsAbs(p) {
+		return p
+	}
+	return filepath.Join(opts.Root, p)
+}
+
+// checkRootPath returns an error if p is relative while --root is set: it
+// would be written to the current directory of this system instead of
+// inside the root, and logged with a path that means nothing there.
+func checkRootPath(p string) error {
+	if opts.Root != "" && p != "-" && !filepath.IsAbs(p) {
+		return fmt.Errorf("cannot install to %s with --root: use an absolute --to or $EGET_BIN inside the root", p)
+	}
+	return nil
+}
@@ -0,0 +1,75 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strconv"
+	"strings"
+)
+
+// runningPids returns the IDs of the processes executing the binary at path.
+// Processes are found through /proc on Linux and with lsof elsewhere; on
+// Windows, and when neither is available, none are found.
+func runningPids(path string) []int {
+	abs, err := filepath.Abs(path)
+	if err != nil {
+		return nil
+	}
+	if resolved, err := filepath.EvalSymlinks(abs); err == nil {
+		abs = resolved
+	}
+
+	pids := []int{}
+	switch runtime.GOOS {
+	case "windows":
+		return nil
+	case "linux":
+		entries, err := os.ReadDir("/proc")
+		if err != nil {
+			return nil
+		}
+		for _, e := range entries {
+			pid, err := strconv.Atoi(e.Name())
+			if err != nil {
+				continue
+			}
+			// processes of other users cannot be inspected and are skipped
+			exe, err := os.Readlink(filepath.Join("/proc", e.Name(), "exe"))
+			if err == nil && exe == abs {
+				pids = append(pids, pid)
+			}
+		}
+		return pids
+	}
+
+	if _, err := exec.LookPath("lsof"); err != nil {
+		return nil
+	}
+	out, _ := exec.Command("lsof", "-t", "--", abs).Output()
+	for _, f := range strings.Fields(string(out)) {
+		if pid, err := strconv.Atoi(f); err == nil {
+			pids = append(pids, pid)
+		}
+	}
+	return pids
+}
+
+// warnRunning warns on stderr if the binary at path is being executed, since
+// replacing or deleting it does not affect the running processes, which keep
+// using the old file until they are restarted. The action is "updating" or
+// "removing".
+func warnRunning(path, action string) {
+	pids := runningPids(path)
+	if len(pids) == 0 {
+		return
+	}
+	ids := make([]string, len(pids))
+	for i, pid := range pids {
+		ids[i] = strconv.Itoa(pid)
+	}
+	fmt.Fprintf(os.Stderr, "Warning: `%s` is running (pid %s); %s it does not affect the running processes until they are restarted\n",
+		path, strings.Join(ids, ", "), action)
+}
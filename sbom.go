@@ -0,0 +1,169 @@
+package main
+
+import (
+	"crypto/rand"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/url"
+	"path"
+	"strings"
+	"time"
+)
+
+// purl returns the package URL of a tool installed from a release, such as
+// pkg:github/owner/repo@v1.0.0, or "" if it was not installed from one.
+func purl(t Tool) string {
+	u, err := url.Parse(repoURL(t.Repo))
+	if err != nil || u.Host == "" {
+		return ""
+	}
+	version := ""
+	if t.Version != "" {
+		version = "@" + url.PathEscape(t.Version)
+	}
+	project := strings.TrimPrefix(u.Path, "/")
+	switch u.Host {
+	case "github.com":
+		return "pkg:github/" + project + version
+	case "gitlab.com":
+		return "pkg:gitlab/" + project + version
+	}
+	// other hosts have no purl type, the repository goes in a qualifier
+	return "pkg:generic/" + path.Base(project) + version + "?vcs_url=" + url.QueryEscape("git+"+u.String())
+}
+
+// sbomTool is a tool to be included in an SBOM, with its checksum.
+type sbomTool struct {
+	Tool
+	SHA256 string
+}
+
+func sbomTools(tools []Tool) []sbomTool {
+	installed := []sbomTool{}
+	for _, t := range tools {
+		if t.Status == StatusInstalled {
+			installed = append(installed, sbomTool{Tool: t})
+		}
+	}
+	paths := make([]string, len(installed))
+	for i, t := range installed {
+		paths[i] = rootPath(t.Path)
+	}
+	sums, _ := fileHashes(paths)
+	for i := range installed {
+		installed[i].SHA256 = sums[i]
+	}
+	return installed
+}
+
+// newUUID returns a random (version 4) UUID.
+func newUUID() string {
+	var b [16]byte
+	rand.Read(b[:])
+	b[6] = b[6]&0x0f | 0x40
+	b[8] = b[8]&0x3f | 0x80
+	return fmt.Sprintf("%x-%x-%x-%x-%x", b[0:4], b[4:6], b[6:8], b[8:10], b[10:])
+}
+
+// WriteSBOM writes a software bill of materials covering every installed
+// tool to w, in the given format: "cyclonedx" (CycloneDX 1.5 JSON) or "spdx"
+// (SPDX 2.3 JSON).
+func WriteSBOM(w io.Writer, tools []Tool, format string) error {
+	var doc interface{}
+	switch format {
+	case "cyclonedx":
+		doc = cyclonedxBOM(sbomTools(tools))
+	case "spdx":
+		doc = spdxDocument(sbomTools(tools))
+	default:
+		return fmt.Errorf("unknown SBOM format '%s' (must be cyclonedx or spdx)", format)
+	}
+	enc := json.NewEncoder(w)
+	enc.SetIndent("", "  ")
+	return enc.Encode(doc)
+}
+
+func cyclonedxBOM(tools []sbomTool) map[string]interface{} {
+	components := []map[string]interface{}{}
+	for _, t := range tools {
+		c := map[string]interface{}{
+			"type":    "application",
+			"bom-ref": t.Path,
+			"name":    t.Name,
+		}
+		if t.Version != "" {
+			c["version"] = t.Version
+		}
+		if p := purl(t.Tool); p != "" {
+			c["purl"] = p
+		}
+		if t.SHA256 != "" {
+			c["hashes"] = []map[string]string{{"alg": "SHA-256", "content": t.SHA256}}
+		}
+		if u := repoURL(t.Repo); u != "" {
+			c["externalReferences"] = []map[string]string{{"type": "vcs", "url": u}}
+		} else if t.Source == SourceURL {
+			c["externalReferences"] = []map[string]string{{"type": "distribution", "url": t.Repo}}
+		}
+		components = append(components, c)
+	}
+	return map[string]interface{}{
+		"bomFormat":    "CycloneDX",
+		"specVersion":  "1.5",
+		"serialNumber": "urn:uuid:" + newUUID(),
+		"version":      1,
+		"metadata": map[string]interface{}{
+			"timestamp": time.Now().UTC().Format(time.RFC3339),
+			"tools": map[string]interface{}{
+				"components": []map[string]string{{"type": "application", "name": "eget", "version": Version}},
+			},
+		},
+		"components": components,
+	}
+}
+
+func spdxDocument(tools []sbomTool) map[string]interface{} {
+	packages := []map[string]interface{}{}
+	for i, t := range tools {
+		download := "NOASSERTION"
+		if u := repoURL(t.Repo); u != "" {
+			download = "git+" + u
+		} else if t.Source == SourceURL {
+			download = t.Repo
+		}
+		p := map[string]interface{}{
+			"SPDXID":           fmt.Sprintf("SPDXRef-Package-%d", i+1),
+			"name":             t.Name,
+			"downloadLocation": download,
+			"filesAnalyzed":    false,
+			"packageFileName":  t.Path,
+		}
+		if t.Version != "" {
+			p["versionInfo"] = t.Version
+		}
+		if t.SHA256 != "" {
+			p["checksums"] = []map[string]string{{"algorithm": "SHA256", "checksumValue": t.SHA256}}
+		}
+		if pu := purl(t.Tool); pu != "" {
+			p["externalRefs"] = []map[string]string{{
+				"referenceCategory": "PACKAGE-MANAGER",
+				"referenceType":     "purl",
+				"referenceLocator":  pu,
+			}}
+		}
+		packages = append(packages, p)
+	}
+	return map[string]interface{}{
+		"spdxVersion":       "SPDX-2.3",
+		"dataLicense":       "CC0-1.0",
+		"SPDXID":            "SPDXRef-DOCUMENT",
+		"name":              "eget-tools",
+		"documentNamespace": "https://spdx.org/spdxdocs/eget-tools-" + newUUID(),
+		"creationInfo": map[string]interface{}{
+			"created":  time.Now().UTC().Format(time.RFC3339),
+			"creators": []string{"Tool: eget-" + Version},
+		},
+		"packages": packages,
+	}
+}
@@ -0,0 +1,193 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strconv"
+	"strings"
+)
+
+// maxSearchResults bounds the number of repositories returned by the search,
+// since checking each one for release assets costs an API request.
+const maxSearchResults = 10
+
+// A GithubRepo matches the parts of Github's repository search json used by
+// --search.
+type GithubRepo struct {
+	FullName    string   `json:"full_name"`
+	Description string   `json:"description"`
+	Topics      []string `json:"topics,omitempty"`
+	Stars       int      `json:"stargazers_count"`
+	Latest      string   `json:"latest"`
+	Installed   bool     `json:"installed"`
+}
+
+type githubSearchResult struct {
+	Items []GithubRepo `json:"items"`
+}
+
+// SearchRepos queries Github for repositories matching query, sorted by stars,
+// and returns the ones whose latest release has assets.
+func SearchRepos(query string) ([]GithubRepo, error) {
+	api := fmt.Sprintf("https://api.github.com/search/repositories?q=%s&sort=stars&per_page=%d",
+		url.QueryEscape(query), maxSearchResults)
+	resp, err := Get(api)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, &GithubError{
+			Status: resp.Status,
+			Code:   resp.StatusCode,
+			Body:   body,
+			Url:    api,
+		}
+	}
+
+	var result githubSearchResult
+	err = json.Unmarshal(body, &result)
+	if err != nil {
+		return nil, err
+	}
+	indexRepos(result.Items)
+
+	return withReleases(result.Items), nil
+}
+
+// withReleases returns the repos whose latest release has assets, with their
+// latest release tag filled in.
+func withReleases(candidates []GithubRepo) []GithubRepo {
+	repos := []GithubRepo{}
+	for _, r := range candidates {
+		finder := &GithubAssetFinder{
+			Repo: r.FullName,
+			Tag:  "latest",
+		}
+		assets, err := finder.Find()
+		if err != nil || len(assets) == 0 {
+			// no release, or a release without prebuilt assets
+			continue
+		}
+		r.Latest = finder.Version
+		repos = append(repos, r)
+	}
+	return repos
+}
+
+// PrintRepos writes search results to w in the given format: "table" (the
+// default), "json" or "porcelain".
+func PrintRepos(w io.Writer, repos []GithubRepo, format string) error {
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2).Limit(0, maxRepoWidth).Limit(3, maxDescriptionWidth)
+		fmt.Fprintln(tw, "REPO\tSTARS\tLATEST\tDESCRIPTION")
+		for _, r := range repos {
+			fmt.Fprintf(tw, "%s\t%d\t%s\t%s\n", r.FullName, r.Stars, r.Latest, r.Description)
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(repos)
+	case "porcelain":
+		for _, r := range repos {
+			writePorcelain(w, r.FullName, strconv.Itoa(r.Stars), r.Latest, r.Description)
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
+
+// maxStarredPages bounds the number of pages of starred repositories fetched,
+// at 100 repositories per page.
+const maxStarredPages = 10
+
+// StarredRepos returns the repositories starred by the authenticated user
+// whose latest release has assets, marking the ones that are installed. A
+// GitHub token is required.
+func StarredRepos(tools []Tool) ([]GithubRepo, error) {
+	if _, err := getGithubToken(); err != nil {
+		return nil, fmt.Errorf("listing starred repositories needs a GitHub token: %w", err)
+	}
+
+	starred := []GithubRepo{}
+	for page := 1; page <= maxStarredPages; page++ {
+		api := fmt.Sprintf("https://api.github.com/user/starred?per_page=100&page=%d", page)
+		resp, err := Get(api)
+		if err != nil {
+			return nil, err
+		}
+		body, err := io.ReadAll(resp.Body)
+		resp.Body.Close()
+		if err != nil {
+			return nil, err
+		}
+		if resp.StatusCode != http.StatusOK {
+			return nil, &GithubError{
+				Status: resp.Status,
+				Code:   resp.StatusCode,
+				Body:   body,
+				Url:    api,
+			}
+		}
+
+		var repos []GithubRepo
+		if err := json.Unmarshal(body, &repos); err != nil {
+			return nil, err
+		}
+		indexRepos(repos)
+		starred = append(starred, repos...)
+		if len(repos) < 100 {
+			break
+		}
+	}
+
+	installed := make(map[string]bool)
+	for _, t := range tools {
+		if t.Status == StatusInstalled {
+			installed[repoKey(t.Repo)] = true
+		}
+	}
+	repos := withReleases(starred)
+	for i := range repos {
+		repos[i].Installed = installed[repoKey(repos[i].FullName)]
+	}
+	return repos, nil
+}
+
+// PrintStarred writes starred repositories to w in the given format: "table"
+// (the default), "json" or "porcelain".
+func PrintStarred(w io.Writer, repos []GithubRepo, format string) error {
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2).Limit(0, maxRepoWidth).Limit(3, maxDescriptionWidth)
+		fmt.Fprintln(tw, "REPO\tLATEST\tINSTALLED\tDESCRIPTION")
+		for _, r := range repos {
+			installed := "no"
+			if r.Installed {
+				installed = "yes"
+			}
+			fmt.Fprintf(tw, "%s\t%s\t%s\t%s\n", r.FullName, r.Latest, installed, r.Description)
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(repos)
+	case "porcelain":
+		for _, r := range repos {
+			writePorcelain(w, r.FullName, r.Latest, strconv.FormatBool(r.Installed), r.Description)
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
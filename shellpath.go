@@ -0,0 +1,47 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// defaultShell returns the name of the user's login shell, or "bash" if it
+// cannot be determined.
+func defaultShell() string {
+	if sh := filepath.Base(os.Getenv("SHELL")); sh == "zsh" || sh == "fish" || sh == "bash" {
+		return sh
+	}
+	return "bash"
+}
+
+// PathSnippet returns a snippet for the given shell (bash, zsh or fish) that
+// adds dir to PATH, and the startup file it belongs in.
+func PathSnippet(shell, dir string) (string, string, error) {
+	abs, err := filepath.Abs(dir)
+	if err != nil {
+		return "", "", err
+	}
+	quoted := shellQuote(abs)
+	switch shell {
+	case "bash":
+		return fmt.Sprintf("export PATH=%s:\"$PATH\"\n", quoted), "~/.bashrc", nil
+	case "zsh":
+		return fmt.Sprintf("export PATH=%s:\"$PATH\"\n", quoted), "~/.zshrc", nil
+	case "fish":
+		return fmt.Sprintf("fish_add_path %s\n", quoted), "~/.config/fish/config.fish", nil
+	}
+	return "", "", fmt.Errorf("unknown shell '%s' (must be bash, zsh or fish)", shell)
+}
+
+// pathFix returns a suggestion for putting dir on PATH in the user's shell.
+func pathFix(dir string) string {
+	shell := defaultShell()
+	snippet, rc, err := PathSnippet(shell, dir)
+	if err != nil {
+		return fmt.Sprintf("add %s to PATH in your shell's startup file", dir)
+	}
+	return fmt.Sprintf("add `%s` to %s, or run `eget --path-snippet %s >> %s`",
+		strings.TrimSpace(snippet), rc, shell, rc)
+}
@@ -0,0 +1,112 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"os/exec"
+	"path"
+	"path/filepath"
+	"strings"
+
+	"github.com/zyedidia/eget/home"
+)
+
+// Kinds of detached signature eget can verify, named after the tool used to
+// check them.
+const (
+	SigMinisign = "minisign"
+	SigGPG      = "gpg"
+)
+
+// signatureAsset searches for a detached signature of asset: an asset with
+// the same name ending in .minisig, .sig or .asc. It returns the signature's
+// URL and kind, or "" if there is none.
+func signatureAsset(asset string, assets []string) (string, string) {
+	for _, a := range assets {
+		switch a {
+		case asset + ".minisig":
+			return a, SigMinisign
+		case asset + ".sig", asset + ".asc":
+			return a, SigGPG
+		}
+	}
+	return "", ""
+}
+
+// VerifySignature checks body, the contents of the asset at assetURL, against
+// the detached signature at sigURL using the given public key. For minisign
+// the key is either the key itself or the path to a .pub file; for gpg it is
+// the path to an exported public key. The minisign or gpg binary must be on
+// PATH.
+func VerifySignature(body []byte, assetURL, sigURL, kind, key string) error {
+	dir, err := os.MkdirTemp("", "eget-sig")
+	if err != nil {
+		return err
+	}
+	defer os.RemoveAll(dir)
+
+	file := filepath.Join(dir, path.Base(assetURL))
+	if err := os.WriteFile(file, body, 0644); err != nil {
+		return err
+	}
+	sig := filepath.Join(dir, path.Base(sigURL))
+	if err := downloadTo(sigURL, sig); err != nil {
+		return fmt.Errorf("signature: %w", err)
+	}
+
+	keyPath, err := home.Expand(key)
+	if err != nil {
+		return err
+	}
+
+	var cmds [][]string
+	switch kind {
+	case SigMinisign:
+		if IsLocalFile(keyPath) {
+			cmds = append(cmds, []string{"minisign", "-V", "-q", "-m", file, "-x", sig, "-p", keyPath})
+		} else {
+			cmds = append(cmds, []string{"minisign", "-V", "-q", "-m", file, "-x", sig, "-P", key})
+		}
+	case SigGPG:
+		// import the key into a throwaway keyring so only it is trusted
+		gpgHome := filepath.Join(dir, "gnupg")
+		if err := os.Mkdir(gpgHome, 0700); err != nil {
+			return err
+		}
+		cmds = append(cmds,
+			[]string{"gpg", "--batch", "--quiet", "--homedir", gpgHome, "--import", keyPath},
+			[]string{"gpg", "--batch", "--quiet", "--homedir", gpgHome, "--verify", sig, file})
+	default:
+		return fmt.Errorf("unknown signature kind '%s'", kind)
+	}
+
+	for _, args := range cmds {
+		out, err := exec.Command(args[0], args[1:]...).CombinedOutput()
+		if err != nil {
+			return fmt.Errorf("%s signature verification failed: %w\n%s", kind, err, strings.TrimSpace(string(out)))
+		}
+	}
+	return nil
+}
+
+// downloadTo downloads url to the file at path.
+func downloadTo(url, path string) error {
+	resp, err := Get(url)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return fmt.Errorf("%s (URL: %s)", resp.Status, url)
+	}
+
+	f, err := os.Create(path)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+	_, err = io.Copy(f, resp.Body)
+	return err
+}
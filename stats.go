@@ -0,0 +1,122 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+)
+
+// maxOwners is the number of repo owners shown by --stats.
+const maxOwners = 5
+
+// A Count is a named counter in the output of --stats.
+type Count struct {
+	Name  string `json:"name"`
+	Count int    `json:"count"`
+}
+
+// Stats are aggregate metrics over the tracked tools and the log.
+type Stats struct {
+	Tools     int     `json:"tools"`
+	DiskUsage int64   `json:"disk_usage"`
+	Statuses  []Count `json:"statuses"`
+	Months    []Count `json:"installs_per_month"`
+	Owners    []Count `json:"top_owners"`
+}
+
+// sortedCounts converts counters to a slice sorted by name.
+func sortedCounts(m map[string]int) []Count {
+	counts := make([]Count, 0, len(m))
+	for name, n := range m {
+		counts = append(counts, Count{Name: name, Count: n})
+	}
+	sort.Slice(counts, func(i, j int) bool {
+		return counts[i].Name < counts[j].Name
+	})
+	return counts
+}
+
+// ComputeStats aggregates the tools and the log entries they were built from.
+func ComputeStats(tools []Tool, entries []LogEntry) Stats {
+	stats := Stats{Tools: len(tools)}
+
+	statuses := make(map[string]int)
+	owners := make(map[string]int)
+	for _, t := range tools {
+		statuses[t.Status]++
+		if t.Status != StatusInstalled {
+			continue
+		}
+		stats.DiskUsage += t.Size
+		if owner, _, found := Cut(t.Repo, "/"); found && !IsUrl(t.Repo) {
+			owners[owner]++
+		}
+	}
+
+	months := make(map[string]int)
+	for _, e := range entries {
+		if e.Action == StatusInstalled {
+			months[e.Timestamp.Local().Format("2006-01")]++
+		}
+	}
+
+	stats.Statuses = sortedCounts(statuses)
+	stats.Months = sortedCounts(months)
+	stats.Owners = sortedCounts(owners)
+	sort.SliceStable(stats.Owners, func(i, j int) bool {
+		return stats.Owners[i].Count > stats.Owners[j].Count
+	})
+	if len(stats.Owners) > maxOwners {
+		stats.Owners = stats.Owners[:maxOwners]
+	}
+	return stats
+}
+
+// PrintStats writes stats to w in the given format: "table" (the default),
+// "json" or "porcelain".
+func PrintStats(w io.Writer, stats Stats, format string) error {
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2)
+		fmt.Fprintf(tw, "Tools:\t%d\n", stats.Tools)
+		fmt.Fprintf(tw, "Disk usage:\t%s\n", formatSize(stats.DiskUsage))
+		sections := []struct {
+			title  string
+			counts []Count
+		}{
+			{"Status", stats.Statuses},
+			{"Installs per month", stats.Months},
+			{"Top owners", stats.Owners},
+		}
+		for _, s := range sections {
+			if len(s.counts) == 0 {
+				continue
+			}
+			fmt.Fprintf(tw, "%s:\t\n", s.title)
+			for _, c := range s.counts {
+				fmt.Fprintf(tw, "  %s\t%d\n", c.Name, c.Count)
+			}
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(stats)
+	case "porcelain":
+		writePorcelain(w, "tools", strconv.Itoa(stats.Tools))
+		writePorcelain(w, "disk_usage", strconv.FormatInt(stats.DiskUsage, 10))
+		for _, c := range stats.Statuses {
+			writePorcelain(w, "status", c.Name, strconv.Itoa(c.Count))
+		}
+		for _, c := range stats.Months {
+			writePorcelain(w, "month", c.Name, strconv.Itoa(c.Count))
+		}
+		for _, c := range stats.Owners {
+			writePorcelain(w, "owner", c.Name, strconv.Itoa(c.Count))
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
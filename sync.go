@@ -0,0 +1,251 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// defaultSyncFile is the name of the manifest in the sync repository or gist.
+const defaultSyncFile = "eget.toml"
+
+// A SyncRemote is a place a manifest is shared through.
+type SyncRemote interface {
+	// Pull returns the contents of the remote manifest, or nil if there is
+	// none yet.
+	Pull() ([]byte, error)
+	// Push replaces the remote manifest with data.
+	Push(data []byte) error
+}
+
+// NewSyncRemote returns the remote configured with sync_repo or sync_gist in
+// the config file.
+func NewSyncRemote(config *Config) (SyncRemote, error) {
+	file := config.Global.SyncFile
+	if file == "" {
+		file = defaultSyncFile
+	}
+	switch {
+	case config.Global.SyncRepo != "" && config.Global.SyncGist != "":
+		return nil, errors.New("only one of sync_repo and sync_gist can be set")
+	case config.Global.SyncRepo != "":
+		dir, err := GetCacheDir()
+		if err != nil {
+			return nil, err
+		}
+		return &gitRemote{
+			URL:  config.Global.SyncRepo,
+			Dir:  filepath.Join(dir, "sync"),
+			File: file,
+		}, nil
+	case config.Global.SyncGist != "":
+		return &gistRemote{ID: config.Global.SyncGist, File: file}, nil
+	}
+	return nil, errors.New("no sync remote: set sync_repo or sync_gist in the config file")
+}
+
+// A gitRemote keeps the manifest in a git repository, through a clone in the
+// cache directory.
+type gitRemote struct {
+	URL  string
+	Dir  string
+	File string
+}
+
+func (g *gitRemote) git(args ...string) error {
+	cmd := exec.Command("git", append([]string{"-C", g.Dir}, args...)...)
+	out, err := cmd.CombinedOutput()
+	if err != nil {
+		return fmt.Errorf("git %s: %w\n%s", args[0], err, strings.TrimSpace(string(out)))
+	}
+	return nil
+}
+
+func (g *gitRemote) Pull() ([]byte, error) {
+	if IsDirectory(filepath.Join(g.Dir, ".git")) {
+		// an empty repository has nothing to pull yet
+		if err := g.git("rev-parse", "--verify", "HEAD"); err == nil {
+			if err := g.git("pull", "--ff-only"); err != nil {
+				return nil, err
+			}
+		}
+	} else {
+		if err := os.MkdirAll(filepath.Dir(g.Dir), 0755); err != nil {
+			return nil, err
+		}
+		out, err := exec.Command("git", "clone", g.URL, g.Dir).CombinedOutput()
+		if err != nil {
+			return nil, fmt.Errorf("git clone: %w\n%s", err, strings.TrimSpace(string(out)))
+		}
+	}
+	data, err := os.ReadFile(filepath.Join(g.Dir, g.File))
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	return data, err
+}
+
+func (g *gitRemote) Push(data []byte) error {
+	if err := os.WriteFile(filepath.Join(g.Dir, g.File), data, 0644); err != nil {
+		return err
+	}
+	if err := g.git("add", g.File); err != nil {
+		return err
+	}
+	if err := g.git("commit", "-m", "Update eget manifest"); err != nil {
+		return err
+	}
+	return g.git("push", "origin", "HEAD")
+}
+
+// A gistRemote keeps the manifest in a file of a GitHub gist. Pushing
+// requires a GitHub token with the gist scope.
+type gistRemote struct {
+	ID   string
+	File string
+}
+
+func (g *gistRemote) api() string {
+	return fmt.Sprintf("https://api.github.com/gists/%s", g.ID)
+}
+
+func (g *gistRemote) Pull() ([]byte, error) {
+	resp, err := Get(g.api())
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, &GithubError{
+			Status: resp.Status,
+			Code:   resp.StatusCode,
+			Body:   body,
+			Url:    g.api(),
+		}
+	}
+
+	var gist struct {
+		Files map[string]struct {
+			Content string `json:"content"`
+		} `json:"files"`
+	}
+	if err := json.Unmarshal(body, &gist); err != nil {
+		return nil, err
+	}
+	f, ok := gist.Files[g.File]
+	if !ok {
+		return nil, nil
+	}
+	return []byte(f.Content), nil
+}
+
+func (g *gistRemote) Push(data []byte) error {
+	payload, err := json.Marshal(map[string]interface{}{
+		"files": map[string]interface{}{
+			g.File: map[string]string{"content": string(data)},
+		},
+	})
+	if err != nil {
+		return err
+	}
+	req, err := http.NewRequest("PATCH", g.api(), bytes.NewReader(payload))
+	if err != nil {
+		return err
+	}
+	req.Header.Set("Accept", "application/vnd.github+json")
+	req = SetAuthHeader(req)
+	if req.Header.Get("Authorization") == "" {
+		return errors.New("updating a gist needs a GitHub token with the gist scope")
+	}
+
+	resp, err := httpClient().Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return &GithubError{
+			Status: resp.Status,
+			Code:   resp.StatusCode,
+			Body:   body,
+			Url:    g.api(),
+		}
+	}
+	return nil
+}
+
+// SyncPlan is what a sync changes on each side.
+type SyncPlan struct {
+	Install []string // repos in the remote manifest that are not installed here
+	Publish []Tool   // installed tools missing from the remote manifest
+}
+
+// PlanSync compares the remote manifest with the installed tools.
+func PlanSync(remote *Config, tools []Tool) SyncPlan {
+	plan := SyncPlan{}
+	extra := make(map[string]bool)
+	for _, d := range DiffManifest(remote, tools) {
+		switch d.Kind {
+		case DiffMissing:
+			plan.Install = append(plan.Install, d.Repo)
+		case DiffExtra:
+			extra[repoKey(d.Repo)] = true
+		}
+	}
+	for _, t := range manifestTools(tools) {
+		if extra[repoKey(t.Repo)] {
+			plan.Publish = append(plan.Publish, t)
+		}
+	}
+	return plan
+}
+
+// PrintSyncPlan describes plan on w.
+func PrintSyncPlan(w io.Writer, plan SyncPlan) {
+	if len(plan.Install) > 0 {
+		fmt.Fprintln(w, "Only in the remote manifest, will be installed here:")
+		for _, repo := range plan.Install {
+			fmt.Fprintf(w, "  + %s\n", repo)
+		}
+	}
+	if len(plan.Publish) > 0 {
+		fmt.Fprintln(w, "Only installed here, will be added to the remote manifest:")
+		for _, t := range plan.Publish {
+			fmt.Fprintf(w, "  + %s\n", t.Repo)
+		}
+	}
+}
+
+// mergeManifest returns the remote manifest with sections appended for the
+// tools to publish. The remote is kept as is so its comments and settings
+// survive the sync. A new manifest records date in its header.
+func mergeManifest(remote []byte, publish []Tool, date time.Time) ([]byte, error) {
+	var b bytes.Buffer
+	if len(remote) == 0 {
+		if err := ExportManifest(&b, publish, false, date); err != nil {
+			return nil, err
+		}
+		return b.Bytes(), nil
+	}
+	b.Write(remote)
+	if !bytes.HasSuffix(remote, []byte("\n")) {
+		b.WriteString("\n")
+	}
+	if err := writeManifestSections(&b, publish, false); err != nil {
+		return nil, err
+	}
+	return b.Bytes(), nil
+}
@@ -0,0 +1,38 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestMergeManifest(t *testing.T) {
+	rg := Tool{Name: "rg", Repo: "BurntSushi/ripgrep", Path: "/opt/eget/bin/rg", Status: StatusInstalled, Version: "14.1.0"}
+	section := "\n# rg\n[\"BurntSushi/ripgrep\"]\n    target = \"/opt/eget/bin\"\n"
+	date := time.Date(2024, 5, 6, 12, 0, 0, 0, time.UTC)
+	header := "# eget manifest, generated by `eget --export` on 2024-05-06.\n" +
+		"# Install or update everything listed here with `eget --apply FILE`.\n"
+	remote := "# shared tools\n[\"zyedidia/eget\"]\n    target = \"~/bin\"\n"
+
+	tests := []struct {
+		name    string
+		remote  string
+		publish []Tool
+		want    string
+	}{
+		{"new manifest", "", []Tool{rg}, header + section},
+		{"appended to remote", remote, []Tool{rg}, remote + section},
+		{"remote without trailing newline", "# shared tools", []Tool{rg}, "# shared tools\n" + section},
+		{"nothing to publish", remote, nil, remote},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := mergeManifest([]byte(tt.remote), tt.publish, date)
+			if err != nil {
+				t.Fatalf("mergeManifest() error = %v", err)
+			}
+			if string(got) != tt.want {
+				t.Errorf("mergeManifest() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
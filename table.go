@@ -0,0 +1,256 @@
+package main
+
+import (
+	"bytes"
+	"io"
+	"os"
+	"strings"
+
+	"github.com/mattn/go-runewidth"
+	"github.com/rivo/uniseg"
+	"golang.org/x/term"
+)
+
+// Widths that cells in table output are truncated to.
+const (
+	maxNameWidth        = 32
+	maxRepoWidth        = 40
+	maxDescriptionWidth = 72
+	maxPathWidth        = 48
+)
+
+// Tables are fit to the terminal by truncating their widest columns, but no
+// column is truncated below minColumnWidth. In terminals narrower than
+// minTerminalWidth the lines are left to wrap instead, since there is no
+// room for a useful table.
+const (
+	minColumnWidth   = 8
+	minTerminalWidth = 40
+)
+
+// A Table aligns tab separated cells into columns like a tabwriter.Writer,
+// but measures cells by their width on the terminal, so repo and file names
+// with wide characters or emoji keep the columns aligned. Cells wider than
+// the limit of their column are truncated with an ellipsis. Text is buffered
+// until Flush.
+type Table struct {
+	w       io.Writer
+	padding int
+	limits  map[int]int
+	middle  map[int]bool
+	wrap    bool
+	buf     bytes.Buffer
+}
+
+// NewTable returns a Table that writes to w with padding spaces between
+// columns.
+func NewTable(w io.Writer, padding int) *Table {
+	return &Table{w: w, padding: padding, limits: make(map[int]int), middle: make(map[int]bool)}
+}
+
+// Limit truncates the cells of column col (counting from 0) to width.
+func (t *Table) Limit(col, width int) *Table {
+	t.limits[col] = width
+	return t
+}
+
+// Middle truncates the cells of column col in the middle rather than at the
+// end, which keeps both the start and the file name of a path.
+func (t *Table) Middle(col int) *Table {
+	t.middle[col] = true
+	return t
+}
+
+// Wrap makes the table wrap its last cells onto continuation lines aligned
+// with their column when they do not fit in the terminal, instead of
+// truncating them. It is meant for tables of details, whose values must be
+// shown in full.
+func (t *Table) Wrap() *Table {
+	t.wrap = true
+	return t
+}
+
+func (t *Table) truncate(col int, c string, width int) string {
+	if runewidth.StringWidth(c) <= width {
+		return c
+	}
+	if t.middle[col] {
+		return truncateMiddle(c, width)
+	}
+	return truncateEnd(c, width, "…")
+}
+
+// graphemes splits s into its grapheme clusters, the characters a user sees,
+// so a letter and its combining accents or emoji joined with ZWJ are never
+// split by truncation or wrapping.
+func graphemes(s string) []string {
+	clusters := []string{}
+	g := uniseg.NewGraphemes(s)
+	for g.Next() {
+		clusters = append(clusters, g.Str())
+	}
+	return clusters
+}
+
+// truncateEnd shortens s to width by replacing its end with tail, like
+// runewidth.Truncate but without splitting grapheme clusters.
+func truncateEnd(s string, width int, tail string) string {
+	if runewidth.StringWidth(s) <= width {
+		return s
+	}
+	width -= runewidth.StringWidth(tail)
+	var b strings.Builder
+	w := 0
+	for _, c := range graphemes(s) {
+		cw := runewidth.StringWidth(c)
+		if w+cw > width {
+			break
+		}
+		b.WriteString(c)
+		w += cw
+	}
+	return b.String() + tail
+}
+
+// truncateMiddle shortens s to width by replacing its middle with an
+// ellipsis.
+func truncateMiddle(s string, width int) string {
+	if width < 3 {
+		return truncateEnd(s, width, "…")
+	}
+	tailWidth := (width - 1) / 2
+	head := truncateEnd(s, width-1-tailWidth, "")
+	clusters := graphemes(s)
+	i, w := len(clusters), 0
+	for i > 0 && w+runewidth.StringWidth(clusters[i-1]) <= tailWidth {
+		i--
+		w += runewidth.StringWidth(clusters[i])
+	}
+	return head + "…" + strings.Join(clusters[i:], "")
+}
+
+// wrapCell splits s into lines of at most width.
+func wrapCell(s string, width int) []string {
+	lines := []string{}
+	var line strings.Builder
+	w := 0
+	for _, c := range graphemes(s) {
+		cw := runewidth.StringWidth(c)
+		if w+cw > width && line.Len() > 0 {
+			lines = append(lines, line.String())
+			line.Reset()
+			w = 0
+		}
+		line.WriteString(c)
+		w += cw
+	}
+	return append(lines, line.String())
+}
+
+func (t *Table) Write(p []byte) (int, error) {
+	return t.buf.Write(p)
+}
+
+// terminalWidth returns the width of the terminal w writes to, or 0 if it is
+// not a terminal. It is read on every call, so a table that is written after
+// the terminal was resized fits its new size.
+func terminalWidth(w io.Writer) int {
+	f, ok := w.(*os.File)
+	if !ok || !isTerminal(f) {
+		return 0
+	}
+	width, _, err := term.GetSize(int(f.Fd()))
+	if err != nil {
+		return 0
+	}
+	return width
+}
+
+// fit narrows the widest of widths, the widths of the columns including the
+// last one, until a line of the table fits in width. It returns false if the
+// table cannot fit without narrowing a column below minColumnWidth, in which
+// case widths may have been narrowed partway and must not be used.
+func fit(widths []int, padding, width int) bool {
+	total := func() int {
+		n := padding * (len(widths) - 1)
+		for _, w := range widths {
+			n += w
+		}
+		return n
+	}
+	for total() > width {
+		widest := 0
+		for i, w := range widths {
+			if w > widths[widest] {
+				widest = i
+			}
+		}
+		if widths[widest] <= minColumnWidth {
+			return false
+		}
+		widths[widest]--
+	}
+	return true
+}
+
+// Flush writes the aligned table. The last cell of a line is never padded.
+// When writing to a terminal, long cells are truncated, or wrapped if the
+// table wraps, so lines fit in it.
+func (t *Table) Flush() error {
+	text := strings.TrimSuffix(t.buf.String(), "\n")
+	t.buf.Reset()
+	if text == "" {
+		return nil
+	}
+
+	rows := [][]string{}
+	widths := []int{}
+	for _, line := range strings.Split(text, "\n") {
+		cells := strings.Split(line, "\t")
+		for i, c := range cells {
+			if max, ok := t.limits[i]; ok {
+				c = t.truncate(i, c, max)
+				cells[i] = c
+			}
+			if i >= len(widths) {
+				widths = append(widths, 0)
+			}
+			if w := runewidth.StringWidth(c); w > widths[i] {
+				widths[i] = w
+			}
+		}
+		rows = append(rows, cells)
+	}
+
+	width := terminalWidth(t.w)
+	if width < minTerminalWidth {
+		width = 0
+	}
+	if fitted := append([]int{}, widths...); width > 0 && !t.wrap && fit(fitted, t.padding, width) {
+		widths = fitted
+		for _, cells := range rows {
+			for i, c := range cells {
+				cells[i] = t.truncate(i, c, widths[i])
+			}
+		}
+	}
+
+	var out strings.Builder
+	for _, cells := range rows {
+		start := 0
+		for i, c := range cells {
+			if i == len(cells)-1 {
+				if avail := width - start; t.wrap && width > 0 && avail >= minColumnWidth {
+					c = strings.Join(wrapCell(c, avail), "\n"+strings.Repeat(" ", start))
+				}
+				out.WriteString(c)
+				break
+			}
+			start += widths[i] + t.padding
+			out.WriteString(runewidth.FillRight(c, widths[i]+t.padding))
+		}
+		out.WriteString("\n")
+	}
+	_, err := io.WriteString(t.w, out.String())
+	return err
+}
@@ -0,0 +1,102 @@
+package main
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestTruncateEnd(t *testing.T) {
+	tests := []struct {
+		name  string
+		s     string
+		width int
+		want  string
+	}{
+		{"fits", "eget", 4, "eget"},
+		{"ascii", "abcdefghij", 5, "abcd…"},
+		{"wide", "日本語テキスト", 5, "日本…"},
+		{"combining mark", "e\u0301tude", 3, "e\u0301t…"},
+		{"zwj emoji", "\U0001F469\u200d\U0001F4BB dev", 3, "\U0001F469\u200d\U0001F4BB…"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := truncateEnd(tt.s, tt.width, "…"); got != tt.want {
+				t.Errorf("truncateEnd(%q, %d) = %q, want %q", tt.s, tt.width, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestTruncateMiddle(t *testing.T) {
+	tests := []struct {
+		name  string
+		s     string
+		width int
+		want  string
+	}{
+		{"odd width", "abcdefghij", 5, "ab…ij"},
+		{"even width", "abcdefghij", 6, "abc…ij"},
+		{"path", "/usr/local/bin/eget", 12, "/usr/l…/eget"},
+		{"too narrow", "abc", 2, "a…"},
+		{"wide", "日本語テキスト", 7, "日…ト"},
+		{"combining marks", "e\u0301e\u0301e\u0301e\u0301e\u0301e\u0301", 5, "e\u0301e\u0301…e\u0301e\u0301"},
+		{"zwj emoji", "\U0001F469\u200d\U0001F4BB\U0001F469\u200d\U0001F4BB\U0001F469\u200d\U0001F4BB\U0001F469\u200d\U0001F4BB", 5, "\U0001F469\u200d\U0001F4BB…\U0001F469\u200d\U0001F4BB"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := truncateMiddle(tt.s, tt.width); got != tt.want {
+				t.Errorf("truncateMiddle(%q, %d) = %q, want %q", tt.s, tt.width, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestWrapCell(t *testing.T) {
+	tests := []struct {
+		name  string
+		s     string
+		width int
+		want  []string
+	}{
+		{"empty", "", 4, []string{""}},
+		{"ascii", "abcdefghij", 4, []string{"abcd", "efgh", "ij"}},
+		{"combining marks", "e\u0301e\u0301e\u0301", 2, []string{"e\u0301e\u0301", "e\u0301"}},
+		{"zwj emoji", "ab\U0001F469\u200d\U0001F4BB", 3, []string{"ab", "\U0001F469\u200d\U0001F4BB"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := wrapCell(tt.s, tt.width); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("wrapCell(%q, %d) = %q, want %q", tt.s, tt.width, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestFit(t *testing.T) {
+	tests := []struct {
+		name    string
+		widths  []int
+		padding int
+		width   int
+		want    []int
+		ok      bool
+	}{
+		{"already fits", []int{10, 20}, 2, 40, []int{10, 20}, true},
+		{"exact fit", []int{10, 20}, 2, 32, []int{10, 20}, true},
+		{"widest narrowed", []int{10, 20}, 2, 26, []int{10, 14}, true},
+		{"ties narrowed in turn", []int{20, 20}, 2, 32, []int{15, 15}, true},
+		{"too narrow", []int{10, 10}, 2, 10, nil, false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			widths := append([]int{}, tt.widths...)
+			ok := fit(widths, tt.padding, tt.width)
+			if ok != tt.ok {
+				t.Fatalf("fit() = %v, want %v", ok, tt.ok)
+			}
+			if ok && !reflect.DeepEqual(widths, tt.want) {
+				t.Errorf("fit() widths = %v, want %v", widths, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,590 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/url"
+	"os"
+	"path"
+	"path/filepath"
+	"runtime"
+	"sort"
+	"strings"
+	"time"
+)
+
+// Statuses a tracked tool can be in, derived from the log and the filesystem.
+const (
+	StatusInstalled = "installed"
+	StatusMissing   = "missing"
+	StatusBroken    = "broken" // a symlink whose target does not exist
+	StatusDisabled  = "disabled"
+	StatusRemoved   = "removed"
+)
+
+// Sources a tracked tool can be installed from, derived from its repo.
+const (
+	SourceRelease = "release" // a release of a GitHub, GitLab or Gitea repo
+	SourceURL     = "url"     // a direct download URL
+	SourceFile    = "file"    // a local file
+)
+
+// ActionUsed is logged when a tool is run through --exec. It only records the
+// time of use and does not change the state of the tool.
+const ActionUsed = "used"
+
+// ActionEnabled is logged when a disabled tool is enabled again, after which
+// it is installed.
+const ActionEnabled = "enabled"
+
+// A Tool is the current state of a binary tracked in the installation log,
+// aggregated from every log entry that refers to the same path.
+type Tool struct {
+	Name      string    `json:"name"`
+	Repo      string    `json:"repo"`
+	Source    string    `json:"source"`
+	Path      string    `json:"path"`
+	Target    string    `json:"target,omitempty"` // what Path links to, if it is a symlink
+	Size      int64     `json:"size"`
+	Status    string    `json:"status"`
+	Version   string    `json:"version,omitempty"`
+	Verified  string    `json:"verified,omitempty"` // kinds of signature and provenance the binary was verified with
+	Installed time.Time `json:"installed"`
+	Updated   time.Time `json:"updated"`
+	LastUsed  time.Time `json:"last_used"`           // zero if never run with --exec
+	Conflicts []string  `json:"conflicts,omitempty"` // other repos that install a binary with the same name
+
+	History []LogEntry `json:"-"` // every log entry for this path, oldest first
+}
+
+// FilterTools returns the tools whose name or repo contains filter, ignoring
+// case, or whose repo has a description or topic containing it in the repo
+// index. An empty filter matches every tool.
+func FilterTools(tools []Tool, filter string) []Tool {
+	if filter == "" {
+		return tools
+	}
+	filter = strings.ToLower(filter)
+	index := readIndex()
+	matched := []Tool{}
+	for _, t := range tools {
+		if strings.Contains(strings.ToLower(t.Name), filter) || strings.Contains(strings.ToLower(t.Repo), filter) ||
+			indexMatches(index, t.Repo, filter) {
+			matched = append(matched, t)
+		}
+	}
+	return matched
+}
+
+// installDir returns the directory executables are installed to: the --to
+// directory if there is one, otherwise $EGET_BIN. It returns "" if binaries
+// are extracted to the current directory.
+func installDir() string {
+	if opts.Output != "" && IsDirectory(rootPath(opts.Output)) {
+		return opts.Output
+	}
+	return os.Getenv("EGET_BIN")
+}
+
+// LoadTools reads the log and returns one Tool per logged path, sorted by
+// name, with its size and status on disk. Entries whose paths differ only in
+// spelling, such as a trailing slash or a "./" prefix, are collapsed into the
+// same Tool; the newest entry determines its state and all of them make up
+// its history.
+func LoadTools() ([]Tool, error) {
+	tools, err := ReadTools()
+	if err != nil {
+		return nil, err
+	}
+	parallel(len(tools), func(i int) {
+		statTool(&tools[i])
+	})
+	return tools, nil
+}
+
+// ReadTools is like LoadTools, but does not look at the files: tools have the
+// status of their last log entry and no size. Commands about a single tool
+// use it and stat only the tools they need, which is much faster with large
+// logs or on network filesystems.
+func ReadTools() ([]Tool, error) {
+	entries, err := ReadLogs()
+	if err != nil {
+		return nil, err
+	}
+
+	index := make(map[string]int)
+	tools := []Tool{}
+	for _, e := range entries {
+		key := filepath.Clean(e.Path)
+		i, ok := index[key]
+		if !ok {
+			i = len(tools)
+			index[key] = i
+			tools = append(tools, Tool{
+				Name:      filepath.Base(e.Path),
+				Path:      e.Path,
+				Installed: e.Timestamp,
+			})
+		}
+		t := &tools[i]
+		t.Path = e.Path
+		if e.Action == ActionUsed {
+			t.LastUsed = e.Timestamp
+			continue
+		}
+		// removals are logged with the file name rather than the repo
+		if e.Action != StatusRemoved {
+			t.Repo = e.Repo
+			t.Version = e.Version
+			t.Verified = e.Verified
+		}
+		t.Status = e.Action
+		t.Updated = e.Timestamp
+		t.History = append(t.History, e)
+	}
+
+	for i := range tools {
+		tools[i].Source = toolSource(tools[i].Repo)
+	}
+	markConflicts(tools)
+
+	sort.SliceStable(tools, func(i, j int) bool {
+		return tools[i].Name < tools[j].Name
+	})
+	return tools, nil
+}
+
+// markConflicts records, for every tool that is not removed, the other repos
+// that install a binary with the same name, either at another path or over
+// the same file earlier in its history.
+func markConflicts(tools []Tool) {
+	repos := make(map[string][]string)
+	add := func(name, repo string) {
+		key := nameKey(name)
+		for _, r := range repos[key] {
+			if repoKey(r) == repoKey(repo) {
+				return
+			}
+		}
+		if repo != "" {
+			repos[key] = append(repos[key], repo)
+		}
+	}
+	for _, t := range tools {
+		if t.Status == StatusRemoved {
+			continue
+		}
+		for _, e := range t.History {
+			if e.Action != StatusRemoved {
+				add(t.Name, e.Repo)
+			}
+		}
+	}
+	for i, t := range tools {
+		if t.Status == StatusRemoved {
+			continue
+		}
+		for _, r := range repos[nameKey(t.Name)] {
+			if repoKey(r) != repoKey(t.Repo) {
+				tools[i].Conflicts = append(tools[i].Conflicts, r)
+			}
+		}
+	}
+}
+
+// commandOwner returns the repo of the tracked tool that the shell runs for
+// the command name, or "" if the first executable named name in PATH is not
+// tracked.
+func commandOwner(tools []Tool, name string) string {
+	found := lookPathAll(name)
+	if len(found) == 0 {
+		return ""
+	}
+	for _, t := range tools {
+		// a tool that is not installed has no file to be the same as
+		if t.Status != StatusRemoved && sameFile(found[0], t.Path) {
+			return t.Repo
+		}
+	}
+	return ""
+}
+
+// toolSource returns where a tool logged with the given repo was installed
+// from.
+func toolSource(repo string) string {
+	if repo == "" {
+		return ""
+	}
+	if isGithubRepo(repo) || IsGithubUrl(repo) || IsGitlabUrl(repo) {
+		return SourceRelease
+	}
+	if _, _, ok := giteaProject(repo, opts.GiteaHosts); ok {
+		return SourceRelease
+	}
+	if IsUrl(repo) {
+		return SourceURL
+	}
+	return SourceFile
+}
+
+// normalizeRepo returns the canonical form of a logged repo: user/repo for
+// GitHub repositories, also when given as a URL, and the project URL for
+// GitLab and Gitea, in both cases without a trailing slash or .git suffix.
+// Direct download URLs and local files are returned unchanged.
+func normalizeRepo(repo string) string {
+	repo = strings.TrimSpace(repo)
+	switch {
+	case IsGithubUrl(repo):
+		_, after, _ := Cut(repo, "github.com/")
+		return strings.TrimSuffix(strings.Trim(after, "/"), ".git")
+	case isGithubRepo(repo):
+		return strings.TrimSuffix(repo, ".git")
+	}
+	if u := repoURL(repo); u != "" {
+		return u
+	}
+	return repo
+}
+
+// repoKey returns the form of repo that is the same for every spelling of
+// it, for comparisons: GitHub, GitLab and Gitea do not distinguish case in
+// repo names.
+func repoKey(repo string) string {
+	if toolSource(repo) == SourceRelease {
+		return strings.ToLower(normalizeRepo(repo))
+	}
+	return normalizeRepo(repo)
+}
+
+// repoURL returns the web URL of the repository a tool with the given repo
+// was released from, or "" if it was not installed from a release.
+func repoURL(repo string) string {
+	if isGithubRepo(repo) {
+		return "https://github.com/" + repo
+	}
+	if IsGithubUrl(repo) {
+		_, after, _ := Cut(repo, "github.com/")
+		return "https://github.com/" + strings.TrimSuffix(strings.Trim(after, "/"), ".git")
+	}
+	if IsGitlabUrl(repo) {
+		host, project := gitlabProject(repo)
+		return "https://" + host + "/" + project
+	}
+	if base, project, ok := giteaProject(repo, opts.GiteaHosts); ok {
+		return base + "/" + project
+	}
+	return ""
+}
+
+// hasProvenance returns true if the build provenance of t was verified when
+// it was installed.
+func hasProvenance(t Tool) bool {
+	for _, kind := range strings.Split(t.Verified, ",") {
+		if kind == ProvAttestation || kind == ProvSigstore {
+			return true
+		}
+	}
+	return false
+}
+
+// displayRepo returns the repo of t as shown in tables. Direct download URLs
+// are shortened to their host and file name, and marked with a 'url:' prefix.
+func displayRepo(t Tool) string {
+	if t.Source != SourceURL {
+		return t.Repo
+	}
+	u, err := url.Parse(t.Repo)
+	if err != nil {
+		return t.Repo
+	}
+	return fmt.Sprintf("url:%s/%s", u.Host, path.Base(u.Path))
+}
+
+// PruneLog removes every log entry that belongs to a tool with one of the
+// given statuses, and returns those tools and the number of malformed lines.
+// If dryRun is true the log is left untouched. Malformed lines are dropped
+// from the log as well, so the log is rewritten when it has any even if no
+// tool is pruned.
+func PruneLog(statuses []string, dryRun bool) ([]Tool, int, error) {
+	tools, err := LoadTools()
+	if err != nil {
+		return nil, 0, err
+	}
+	malformed, err := countMalformed()
+	if err != nil {
+		return nil, 0, err
+	}
+
+	dead := make(map[string]bool)
+	pruned := []Tool{}
+	for _, t := range tools {
+		for _, s := range statuses {
+			if t.Status == s {
+				dead[filepath.Clean(t.Path)] = true
+				pruned = append(pruned, t)
+				break
+			}
+		}
+	}
+	if dryRun || (len(pruned) == 0 && malformed == 0) {
+		return pruned, malformed, nil
+	}
+
+	entries, err := ReadLogs()
+	if err != nil {
+		return nil, 0, err
+	}
+	kept := make([]LogEntry, 0, len(entries))
+	for _, e := range entries {
+		if !dead[filepath.Clean(e.Path)] {
+			kept = append(kept, e)
+		}
+	}
+	return pruned, malformed, WriteLogs(kept)
+}
+
+// isExecutable returns true if fi looks like an executable file.
+func isExecutable(fi os.FileInfo) bool {
+	if !fi.Mode().IsRegular() {
+		return false
+	}
+	return fi.Mode()&0111 != 0 || strings.EqualFold(filepath.Ext(fi.Name()), ".exe")
+}
+
+// OrphanedFiles returns the executables in dir that are not referenced by
+// any log entry.
+func OrphanedFiles(dir string) ([]string, error) {
+	entries, err := ReadLogs()
+	if err != nil {
+		return nil, err
+	}
+	tracked := make(map[string]bool)
+	for _, e := range entries {
+		if abs, err := filepath.Abs(e.Path); err == nil {
+			tracked[abs] = true
+			tracked[abs+disabledSuffix] = true
+		}
+	}
+
+	files, err := os.ReadDir(rootPath(dir))
+	if err != nil {
+		return nil, err
+	}
+	orphans := []string{}
+	for _, f := range files {
+		fi, err := f.Info()
+		if err != nil || !isExecutable(fi) {
+			continue
+		}
+		p := filepath.Join(dir, f.Name())
+		if abs, err := filepath.Abs(p); err == nil && !tracked[abs] {
+			orphans = append(orphans, p)
+		}
+	}
+	return orphans, nil
+}
+
+// matchesTool returns true if name refers to t, either by its binary name, its
+// full repo or the name of its repo.
+func matchesTool(t Tool, name string) bool {
+	if sameName(t.Name, name) || repoKey(t.Repo) == repoKey(name) {
+		return true
+	}
+	parts := strings.Split(t.Repo, "/")
+	return sameName(parts[len(parts)-1], name)
+}
+
+// sameName returns true if the binary names a and b are the same. On Windows
+// names are case insensitive and the .exe suffix is optional, so "rg" and
+// "RG.exe" are the same.
+func sameName(a, b string) bool {
+	return nameKey(a) == nameKey(b)
+}
+
+// nameKey returns the form of a binary name that is the same for all names
+// that sameName considers equal.
+func nameKey(name string) string {
+	if runtime.GOOS != "windows" {
+		return name
+	}
+	return strings.TrimSuffix(strings.ToLower(name), ".exe")
+}
+
+// FindTool returns the tool matching name. Tools that are installed are
+// preferred over missing or removed ones.
+func FindTool(tools []Tool, name string) (Tool, bool) {
+	var found Tool
+	ok := false
+	for _, t := range tools {
+		if !matchesTool(t, name) {
+			continue
+		}
+		if t.Status == StatusInstalled {
+			return t, true
+		}
+		if !ok {
+			found, ok = t, true
+		}
+	}
+	return found, ok
+}
+
+// statTool fills in the size and on-disk status of a tool that has not been
+// removed. For a symlink, the size is that of the file it resolves to.
+func statTool(t *Tool) {
+	if t.Status == StatusRemoved {
+		return
+	}
+	p := rootPath(t.Path)
+	if t.Status == StatusDisabled {
+		p += disabledSuffix
+	}
+	li, err := os.Lstat(p)
+	if err != nil {
+		t.Status = StatusMissing
+		return
+	}
+	if li.Mode()&os.ModeSymlink != 0 {
+		target, err := filepath.EvalSymlinks(p)
+		if err != nil {
+			t.Target, _ = os.Readlink(p)
+			t.Status = StatusBroken
+			return
+		}
+		t.Target = target
+	}
+	fi, err := os.Stat(p)
+	if err != nil {
+		t.Status = StatusMissing
+		return
+	}
+	if t.Status != StatusDisabled {
+		t.Status = StatusInstalled
+	}
+	t.Size = fi.Size()
+}
+
+// formatSize returns a human readable representation of n bytes.
+func formatSize(n int64) string {
+	const unit = 1024
+	if n < unit {
+		return fmt.Sprintf("%d B", n)
+	}
+	div, exp := int64(unit), 0
+	for m := n / unit; m >= unit; m /= unit {
+		div *= unit
+		exp++
+	}
+	return fmt.Sprintf("%.1f %ciB", float64(n)/float64(div), "KMGTPE"[exp])
+}
+
+// formatTime returns t in the local time zone, for tables meant for humans.
+// The log and machine-readable formats keep times in UTC.
+func formatTime(t time.Time) string {
+	return t.Local().Format("2006-01-02 15:04:05 MST")
+}
+
+// formatRelative returns how long ago t was, such as "3 days ago".
+func formatRelative(t time.Time) string {
+	plural := func(n int, unit string) string {
+		if n == 1 {
+			return fmt.Sprintf("1 %s ago", unit)
+		}
+		return fmt.Sprintf("%d %ss ago", n, unit)
+	}
+	d := time.Since(t)
+	switch {
+	case d < time.Minute:
+		return "just now"
+	case d < time.Hour:
+		return plural(int(d/time.Minute), "minute")
+	case d < 24*time.Hour:
+		return plural(int(d/time.Hour), "hour")
+	case d < 30*24*time.Hour:
+		return plural(int(d/(24*time.Hour)), "day")
+	case d < 365*24*time.Hour:
+		return plural(int(d/(30*24*time.Hour)), "month")
+	}
+	return plural(int(d/(365*24*time.Hour)), "year")
+}
+
+// PrintHistory writes the log entries of a tool to w in the given format:
+// "table" (the default), "json" or "porcelain".
+func PrintHistory(w io.Writer, history []LogEntry, format string) error {
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2).Limit(3, maxRepoWidth).Limit(4, maxPathWidth).Middle(4)
+		fmt.Fprintln(tw, "TIME\tACTION\tVERSION\tREPO\tPATH")
+		for _, e := range history {
+			version := e.Version
+			if version == "" {
+				version = "-"
+			}
+			fmt.Fprintf(tw, "%s\t%s\t%s\t%s\t%s\n",
+				formatTime(e.Timestamp), e.Action, version, e.Repo, e.Path)
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(history)
+	case "porcelain":
+		for _, e := range history {
+			writePorcelain(w, porcelainTime(e.Timestamp), e.Action, e.Version, e.Repo, e.Path)
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
+
+// PrintTools writes the tools to w in the given format: "table" (the
+// default), "json", "tsv" or "porcelain".
+func PrintTools(w io.Writer, tools []Tool, format string) error {
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2).Limit(0, maxNameWidth).Limit(1, maxRepoWidth).Limit(6, maxPathWidth).Middle(6)
+		fmt.Fprintln(tw, "NAME\tREPO\tVERSION\tSTATUS\tSIZE\tUPDATED\tPATH")
+		for _, t := range tools {
+			size := "-"
+			if t.Status == StatusInstalled {
+				size = formatSize(t.Size)
+			}
+			version := t.Version
+			if version == "" {
+				version = "-"
+			}
+			if hasProvenance(t) {
+				version += " ✓"
+			}
+			path := t.Path
+			if t.Target != "" {
+				path += " -> " + t.Target
+			}
+			status := t.Status
+			if len(t.Conflicts) > 0 {
+				status += " (conflict)"
+			}
+			fmt.Fprintf(tw, "%s\t%s\t%s\t%s\t%s\t%s\t%s\n",
+				t.Name, displayRepo(t), version, status, size, formatRelative(t.Updated), path)
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(tools)
+	case "tsv":
+		for _, t := range tools {
+			fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%d\t%s\t%s\t%s\n",
+				t.Name, t.Repo, t.Path, t.Status, t.Size,
+				t.Installed.Format(time.RFC3339), t.Updated.Format(time.RFC3339), t.Version)
+		}
+		return nil
+	case "porcelain":
+		for _, t := range tools {
+			porcelainTool(w, t)
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json, tsv or porcelain)", format)
+}
@@ -0,0 +1,194 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+	"time"
+)
+
+// useLog points eget at a log with the given contents under a temporary
+// --root for the rest of the test, and returns the path of the log. An empty
+// log is not created.
+func useLog(t *testing.T, log string) string {
+	saved := opts.Root
+	t.Cleanup(func() { opts.Root = saved })
+	opts.Root = t.TempDir()
+
+	logFile, err := GetLogFilePath()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if log != "" {
+		if err := os.MkdirAll(filepath.Dir(logFile), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(logFile, []byte(log), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	return logFile
+}
+
+func TestPruneLog(t *testing.T) {
+	const (
+		egetLine    = "2024-01-01T00:00:00Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\tv1.3.3\n"
+		rgLine      = "2024-01-01T00:00:00Z\tBurntSushi/ripgrep\t/usr/local/bin/rg\tinstalled\n"
+		rgRemoved   = "2024-02-01T00:00:00Z\trg\t/usr/local/bin/rg\tremoved\n"
+		garbageLine = "garbage\n"
+	)
+
+	tests := []struct {
+		name      string
+		log       string
+		statuses  []string
+		pruned    []string
+		malformed int
+		want      string
+	}{
+		{"clean log", egetLine, []string{StatusRemoved}, []string{}, 0, egetLine},
+		{"only malformed lines", garbageLine + "not\ta\tlog\n", []string{StatusRemoved}, []string{}, 2, ""},
+		{"malformed line and nothing to prune", egetLine + garbageLine, []string{StatusRemoved}, []string{}, 1, egetLine},
+		{"removed tool", egetLine + rgLine + rgRemoved, []string{StatusRemoved}, []string{"/usr/local/bin/rg"}, 0, egetLine},
+		{"removed tool and malformed line", rgLine + garbageLine + rgRemoved, []string{StatusRemoved}, []string{"/usr/local/bin/rg"}, 1, ""},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			logFile := useLog(t, tt.log)
+
+			pruned, malformed, err := PruneLog(tt.statuses, false)
+			if err != nil {
+				t.Fatalf("PruneLog() error = %v", err)
+			}
+			paths := []string{}
+			for _, p := range pruned {
+				paths = append(paths, p.Path)
+			}
+			if !reflect.DeepEqual(paths, tt.pruned) || malformed != tt.malformed {
+				t.Errorf("PruneLog() = %v, %d, want %v, %d", paths, malformed, tt.pruned, tt.malformed)
+			}
+			data, err := os.ReadFile(logFile)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if string(data) != tt.want {
+				t.Errorf("log = %q, want %q", data, tt.want)
+			}
+		})
+	}
+}
+
+func TestReadTools(t *testing.T) {
+	ts := func(s string) time.Time {
+		v, err := time.Parse(time.RFC3339, s)
+		if err != nil {
+			panic(err)
+		}
+		return v
+	}
+	installed := LogEntry{Timestamp: ts("2024-01-01T00:00:00Z"), Repo: "zyedidia/eget", Path: "/usr/local/bin/eget", Action: StatusInstalled, Version: "v1.3.2"}
+	updated := LogEntry{Timestamp: ts("2024-02-01T00:00:00Z"), Repo: "zyedidia/eget", Path: "/usr/local/bin/./eget", Action: StatusInstalled, Version: "v1.3.3", Verified: "sha256"}
+	rg := LogEntry{Timestamp: ts("2024-01-15T00:00:00Z"), Repo: "BurntSushi/ripgrep", Path: "/usr/local/bin/rg", Action: StatusInstalled, Version: "14.1.0"}
+	rgRemoved := LogEntry{Timestamp: ts("2024-04-01T00:00:00Z"), Repo: "rg", Path: "/usr/local/bin/rg", Action: StatusRemoved}
+
+	tests := []struct {
+		name string
+		log  string
+		want []Tool
+	}{
+		{"no log", "", []Tool{}},
+		{
+			"aggregated by path",
+			"2024-01-01T00:00:00Z\tzyedidia/eget\t/usr/local/bin/eget\tinstalled\tv1.3.2\n" +
+				"2024-01-15T00:00:00Z\tBurntSushi/ripgrep\t/usr/local/bin/rg\tinstalled\t14.1.0\n" +
+				"2024-02-01T00:00:00Z\thttps://github.com/zyedidia/eget\t/usr/local/bin/./eget\tinstalled\tv1.3.3\tsha256\n" +
+				"2024-03-01T00:00:00Z\tzyedidia/eget\t/usr/local/bin/eget\tused\n" +
+				"2024-04-01T00:00:00Z\trg\t/usr/local/bin/rg\tremoved\n",
+			[]Tool{
+				{
+					Name:      "eget",
+					Repo:      "zyedidia/eget",
+					Source:    SourceRelease,
+					Path:      "/usr/local/bin/eget",
+					Status:    StatusInstalled,
+					Version:   "v1.3.3",
+					Verified:  "sha256",
+					Installed: installed.Timestamp,
+					Updated:   updated.Timestamp,
+					LastUsed:  ts("2024-03-01T00:00:00Z"),
+					History:   []LogEntry{installed, updated},
+				},
+				{
+					Name:      "rg",
+					Repo:      "BurntSushi/ripgrep",
+					Source:    SourceRelease,
+					Path:      "/usr/local/bin/rg",
+					Status:    StatusRemoved,
+					Version:   "14.1.0",
+					Installed: rg.Timestamp,
+					Updated:   rgRemoved.Timestamp,
+					History:   []LogEntry{rg, rgRemoved},
+				},
+			},
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			useLog(t, tt.log)
+
+			got, err := ReadTools()
+			if err != nil {
+				t.Fatalf("ReadTools() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("ReadTools() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestNormalizeRepo(t *testing.T) {
+	tests := []struct {
+		in   string
+		want string
+	}{
+		{"zyedidia/eget", "zyedidia/eget"},
+		{" zyedidia/eget ", "zyedidia/eget"},
+		{"zyedidia/eget.git", "zyedidia/eget"},
+		{"https://github.com/zyedidia/eget", "zyedidia/eget"},
+		{"https://github.com/zyedidia/eget.git", "zyedidia/eget"},
+		{"github.com/zyedidia/eget/", "zyedidia/eget"},
+		{"https://gitlab.com/group/sub/project.git", "https://gitlab.com/group/sub/project"},
+		{"https://example.com/tool-1.0.tar.gz", "https://example.com/tool-1.0.tar.gz"},
+		{"/tmp/tool", "/tmp/tool"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.in, func(t *testing.T) {
+			if got := normalizeRepo(tt.in); got != tt.want {
+				t.Errorf("normalizeRepo(%q) = %q, want %q", tt.in, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestRepoKey(t *testing.T) {
+	tests := []struct {
+		in   string
+		want string
+	}{
+		{"zyedidia/eget", "zyedidia/eget"},
+		{"Zyedidia/Eget", "zyedidia/eget"},
+		{"https://github.com/Zyedidia/Eget.git", "zyedidia/eget"},
+		{"https://gitlab.com/Group/Project", "https://gitlab.com/group/project"},
+		{"https://example.com/Tool.tar.gz", "https://example.com/Tool.tar.gz"},
+		{"/tmp/Tool", "/tmp/Tool"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.in, func(t *testing.T) {
+			if got := repoKey(tt.in); got != tt.want {
+				t.Errorf("repoKey(%q) = %q, want %q", tt.in, got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,197 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"net/url"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strconv"
+	"strings"
+	"syscall"
+	"time"
+
+	"github.com/zyedidia/eget/home"
+)
+
+// removeFile deletes the file at path: permanently if --permanent or
+// permanent_delete is set, otherwise by moving it to the trash.
+func removeFile(path string) error {
+	if opts.Permanent {
+		err := os.Remove(path)
+		if err != nil && runtime.GOOS == "windows" && !os.IsNotExist(err) {
+			// a running executable cannot be deleted on Windows, but it can
+			// be renamed out of the way; the next install over it removes it
+			os.Remove(path + ".old")
+			if os.Rename(path, path+".old") == nil {
+				return nil
+			}
+		}
+		return err
+	}
+	if _, err := os.Lstat(path); err != nil {
+		return err
+	}
+	if err := Trash(path); err != nil {
+		if runtime.GOOS != "darwin" {
+			return fmt.Errorf("cannot move %s to the trash (use --permanent to delete it): %w", path, err)
+		}
+		// a Mac whose trash cannot be reached at all is most often used
+		// headless, where refusing would make --remove unusable
+		fmt.Fprintf(os.Stderr, "Warning: cannot move %s to the trash, deleting it permanently: %v\n", path, err)
+		return os.Remove(path)
+	}
+	return nil
+}
+
+// Trash moves the file at path to the trash of the desktop: the Finder's on
+// macOS, the Recycle Bin on Windows and the freedesktop.org trash elsewhere.
+func Trash(path string) error {
+	abs, err := filepath.Abs(path)
+	if err != nil {
+		return err
+	}
+	switch runtime.GOOS {
+	case "darwin":
+		script := fmt.Sprintf("tell application \"Finder\" to delete POSIX file %s", appleScriptString(abs))
+		if err := runQuiet(exec.Command("osascript", "-e", script)); err != nil {
+			// the Finder cannot be asked over ssh, in launchd jobs and in
+			// other headless sessions
+			return macTrash(abs)
+		}
+		return nil
+	case "windows":
+		script := fmt.Sprintf("Add-Type -AssemblyName Microsoft.VisualBasic; "+
+			"[Microsoft.VisualBasic.FileIO.FileSystem]::DeleteFile(%s, 'OnlyErrorDialogs', 'SendToRecycleBin')", powershellString(abs))
+		return runQuiet(exec.Command("powershell", "-NoProfile", "-NonInteractive", "-Command", script))
+	}
+	return freedesktopTrash(abs)
+}
+
+func runQuiet(cmd *exec.Cmd) error {
+	if out, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("%w: %s", err, strings.TrimSpace(string(out)))
+	}
+	return nil
+}
+
+// freedesktopTrash moves the file at the absolute path into the home trash
+// ($XDG_DATA_HOME/Trash) as described by the freedesktop.org trash
+// specification, so it can be restored from the file manager.
+func freedesktopTrash(abs string) error {
+	dataHome := os.Getenv("XDG_DATA_HOME")
+	if dataHome == "" {
+		homeDir, err := home.Home()
+		if err != nil {
+			return err
+		}
+		dataHome = filepath.Join(homeDir, ".local", "share")
+	}
+	trash := filepath.Join(dataHome, "Trash")
+	if err := os.MkdirAll(filepath.Join(trash, "files"), 0700); err != nil {
+		return err
+	}
+	if err := os.MkdirAll(filepath.Join(trash, "info"), 0700); err != nil {
+		return err
+	}
+
+	// reserve a unique name by creating its info file exclusively
+	base := filepath.Base(abs)
+	var name string
+	var info *os.File
+	for i := 1; ; i++ {
+		name = base
+		if i > 1 {
+			name = base + "." + strconv.Itoa(i)
+		}
+		f, err := os.OpenFile(filepath.Join(trash, "info", name+".trashinfo"), os.O_WRONLY|os.O_CREATE|os.O_EXCL, 0600)
+		if err == nil {
+			info = f
+			break
+		} else if !os.IsExist(err) {
+			return err
+		}
+	}
+	fmt.Fprintf(info, "[Trash Info]\nPath=%s\nDeletionDate=%s\n",
+		(&url.URL{Path: abs}).EscapedPath(), time.Now().Format("2006-01-02T15:04:05"))
+	info.Close()
+
+	dest := filepath.Join(trash, "files", name)
+	err := os.Rename(abs, dest)
+	if errors.Is(err, syscall.EXDEV) {
+		// the home trash is on another filesystem, as it is for binaries
+		// in /usr/local/bin on many systems
+		err = moveAcrossDevices(abs, dest)
+	}
+	if err != nil {
+		os.Remove(filepath.Join(trash, "info", name+".trashinfo"))
+	}
+	return err
+}
+
+// macTrash moves the file at the absolute path into ~/.Trash directly. Unlike
+// the Finder, it does not record where the file came from, so it cannot be
+// put back automatically.
+func macTrash(abs string) error {
+	homeDir, err := home.Home()
+	if err != nil {
+		return err
+	}
+	trash := filepath.Join(homeDir, ".Trash")
+	if err := os.MkdirAll(trash, 0700); err != nil {
+		return err
+	}
+
+	// name clashes are resolved like the Finder does, with a number suffix
+	base := filepath.Base(abs)
+	dest := filepath.Join(trash, base)
+	for i := 2; ; i++ {
+		if _, err := os.Lstat(dest); os.IsNotExist(err) {
+			break
+		} else if err != nil {
+			return err
+		}
+		dest = filepath.Join(trash, fmt.Sprintf("%s %d", base, i))
+	}
+
+	err = os.Rename(abs, dest)
+	if errors.Is(err, syscall.EXDEV) {
+		err = moveAcrossDevices(abs, dest)
+	}
+	return err
+}
+
+// moveAcrossDevices copies the file at src to dst, keeping its mode, and
+// removes src.
+func moveAcrossDevices(src, dst string) error {
+	in, err := os.Open(src)
+	if err != nil {
+		return err
+	}
+	defer in.Close()
+	fi, err := in.Stat()
+	if err != nil {
+		return err
+	}
+	out, err := os.OpenFile(dst, os.O_WRONLY|os.O_CREATE|os.O_EXCL, fi.Mode().Perm())
+	if err != nil {
+		return err
+	}
+	if _, err := io.Copy(out, in); err != nil {
+		out.Close()
+		os.Remove(dst)
+		return err
+	}
+	if err := out.Close(); err != nil {
+		os.Remove(dst)
+		return err
+	}
+	if err := os.Remove(src); err != nil {
+		os.Remove(dst)
+		return err
+	}
+	return nil
+}
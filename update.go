@@ -0,0 +1,198 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"strings"
+	"time"
+)
+
+// An Update is a tracked tool for which a newer release is available.
+type Update struct {
+	Tool
+	Latest string `json:"latest"`
+}
+
+// isGithubRepo returns true if repo has the form user/repo.
+func isGithubRepo(repo string) bool {
+	parts := strings.Split(repo, "/")
+	return !IsUrl(repo) && len(parts) == 2 && parts[0] != "" && parts[1] != ""
+}
+
+// latestReleaseFinder returns a finder for the latest release of repo, or nil
+// if repo is not hosted somewhere eget can query for releases. Gitea
+// repositories are looked up on the given hosts.
+func latestReleaseFinder(repo string, hosts []string, mint time.Time) ReleaseFinder {
+	if base, project, ok := giteaProject(repo, hosts); ok {
+		return &GiteaAssetFinder{
+			Base:    base,
+			Repo:    project,
+			Tag:     "latest",
+			MinTime: mint,
+		}
+	}
+	if IsGitlabUrl(repo) {
+		host, project := gitlabProject(repo)
+		return &GitlabAssetFinder{
+			Host:    host,
+			Project: project,
+			Tag:     "latest",
+			MinTime: mint,
+		}
+	}
+	if !isGithubRepo(repo) {
+		return nil
+	}
+	return &GithubAssetFinder{
+		Repo:    repo,
+		Tag:     "latest",
+		MinTime: mint,
+	}
+}
+
+// urlModTime returns the Last-Modified time the server reports for a direct
+// download URL.
+func urlModTime(u string) (time.Time, error) {
+	resp, err := Head(u)
+	if err != nil {
+		return time.Time{}, err
+	}
+	resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return time.Time{}, fmt.Errorf("%s (URL: %s)", resp.Status, u)
+	}
+	return http.ParseTime(resp.Header.Get("Last-Modified"))
+}
+
+// CheckUpdates queries the latest release of every installed tool that came
+// from a GitHub, GitLab or Gitea repo and returns the ones that are out of
+// date, in the order of tools. Up to max_requests tools are checked at the
+// same time. Tools pinned to a tag in the config file are skipped, and so are
+// tools that mise or asdf also manage if skip_version_managed is set. When the
+// installed version is not known, a tool is out of date if the release is
+// newer than the file. Tools installed from a direct URL are out of date if
+// the server reports the file as modified after it was installed; their
+// latest version is the modification date. Tools built for an architecture
+// this system cannot run are always out of date, so updating them installs
+// the build for this system.
+func CheckUpdates(tools []Tool, config *Config) ([]Update, []error) {
+	hosts := giteaHosts(config)
+	results := make([]*Update, len(tools))
+	failed := make([]error, len(tools))
+	parallelLimit(len(tools), opts.MaxRequests, func(i int) {
+		results[i], failed[i] = checkUpdate(tools[i], config, hosts)
+	})
+
+	updates := []Update{}
+	errs := []error{}
+	// once a host is rate limited, every later check fails the same way, so
+	// it is reported once
+	limited := make(map[string]int)
+	for i := range tools {
+		var rl *RateLimitError
+		if errors.As(failed[i], &rl) {
+			if limited[rl.Host]++; limited[rl.Host] > 1 {
+				continue
+			}
+		}
+		if failed[i] != nil {
+			errs = append(errs, failed[i])
+		} else if results[i] != nil {
+			updates = append(updates, *results[i])
+		}
+	}
+	for i, err := range errs {
+		var rl *RateLimitError
+		if errors.As(err, &rl) && limited[rl.Host] > 1 {
+			errs[i] = fmt.Errorf("%d tools not checked: %w", limited[rl.Host], rl)
+		}
+	}
+	return updates, errs
+}
+
+// checkUpdate returns the update available for t, or nil if it is up to date
+// or not checked.
+func checkUpdate(t Tool, config *Config, hosts []string) (*Update, error) {
+	if t.Status != StatusInstalled {
+		return nil, nil
+	}
+	if repo, ok := repoConfig(config, t.Repo); ok && repo.Tag != "" {
+		return nil, nil
+	}
+	if manager, _ := versionManager(t.Name); config.Global.SkipManaged && manager != "" {
+		return nil, nil
+	}
+
+	if t.Source == SourceURL {
+		modified, err := urlModTime(t.Repo)
+		if err != nil {
+			return nil, fmt.Errorf("%s: %w", t.Repo, err)
+		} else if modified.After(t.Updated) {
+			return &Update{Tool: t, Latest: modified.Format("2006-01-02")}, nil
+		}
+		return nil, nil
+	}
+
+	var mint time.Time
+	if t.Version == "" {
+		if fi, err := os.Stat(rootPath(t.Path)); err == nil {
+			mint = fi.ModTime()
+		}
+	}
+	finder := latestReleaseFinder(t.Repo, hosts, mint)
+	if finder == nil {
+		return nil, nil
+	}
+
+	_, err := finder.Find()
+	if errors.Is(err, ErrNoUpgrade) {
+		return nil, nil
+	} else if err != nil {
+		return nil, fmt.Errorf("%s: %w", t.Repo, err)
+	}
+	// a build for another architecture is replaced by the host's
+	if t.Version == "" || finder.ReleaseTag() != t.Version || (opts.Root == "" && len(archMismatch(t.Path)) > 0) {
+		return &Update{Tool: t, Latest: finder.ReleaseTag()}, nil
+	}
+	return nil, nil
+}
+
+// updateArgs returns the arguments that make eget install the latest release
+// of an update over the current file. u.Path is passed rather than the file
+// it links to, so the update is logged for the tracked path; the install
+// writes through a symlink and keeps it.
+func updateArgs(u Update) []string {
+	return []string{"--quiet", "--to", u.Path, u.Repo}
+}
+
+// PrintUpdates writes the available updates to w in the given format:
+// "table" (the default), "json" or "porcelain".
+func PrintUpdates(w io.Writer, updates []Update, format string) error {
+	switch format {
+	case "", "table":
+		tw := NewTable(w, 2).Limit(0, maxNameWidth).Limit(1, maxRepoWidth)
+		fmt.Fprintln(tw, "NAME\tREPO\tCURRENT\tLATEST")
+		for _, u := range updates {
+			current := u.Version
+			if current == "" {
+				current = "-"
+			}
+			fmt.Fprintf(tw, "%s\t%s\t%s\t%s\n", u.Name, displayRepo(u.Tool), current, u.Latest)
+		}
+		return tw.Flush()
+	case "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		return enc.Encode(updates)
+	case "porcelain":
+		for _, u := range updates {
+			writePorcelain(w, u.Name, u.Repo, u.Path, u.Version, u.Latest)
+		}
+		return nil
+	}
+	return fmt.Errorf("unknown format '%s' (must be table, json or porcelain)", format)
+}
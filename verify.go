@@ -1,11 +1,15 @@
 package main
 
 import (
+	"bufio"
 	"bytes"
 	"crypto/sha256"
 	"encoding/hex"
+	"errors"
 	"fmt"
 	"io"
+	"net/http"
+	"strings"
 )
 
 type Verifier interface {
@@ -88,3 +92,62 @@ func (s256 *Sha256AssetVerifier) Verify(b []byte) error {
 		Got:      sum[:],
 	}
 }
+
+// ErrNotListed is returned by a Sha256SumsVerifier when the checksum file
+// has no entry for the asset.
+var ErrNotListed = errors.New("asset not listed in checksum file")
+
+// A Sha256SumsVerifier verifies an asset against a checksum file covering
+// every asset of a release, such as SHA256SUMS or checksums.txt, in the
+// format written by sha256sum: one '<hex>  <name>' line per file.
+type Sha256SumsVerifier struct {
+	AssetURL string // URL of the checksum file
+	Asset    string // file name of the asset to verify
+}
+
+func (s256 *Sha256SumsVerifier) Verify(b []byte) error {
+	resp, err := Get(s256.AssetURL)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	// an error page must not be taken for a file without the asset
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return &GithubError{
+			Status: resp.Status,
+			Code:   resp.StatusCode,
+			Body:   body,
+			Url:    s256.AssetURL,
+		}
+	}
+
+	scanner := bufio.NewScanner(resp.Body)
+	for scanner.Scan() {
+		fields := strings.Fields(scanner.Text())
+		if len(fields) != 2 {
+			continue
+		}
+		// sha256sum marks files hashed in binary mode with '*'
+		name := strings.TrimPrefix(fields[1], "*")
+		if name != s256.Asset && !strings.HasSuffix(name, "/"+s256.Asset) {
+			continue
+		}
+		expected, err := hex.DecodeString(fields[0])
+		if err != nil || len(expected) != sha256.Size {
+			return fmt.Errorf("invalid sha256 checksum for %s: %s", s256.Asset, fields[0])
+		}
+		sum := sha256.Sum256(b)
+		if bytes.Equal(sum[:], expected) {
+			return nil
+		}
+		return &Sha256Error{
+			Expected: expected,
+			Got:      sum[:],
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		return err
+	}
+	return ErrNotListed
+}
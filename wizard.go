@@ -0,0 +1,116 @@
+package main
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"regexp"
+	"strings"
+)
+
+// A wizard asks for the options of an install one at a time on the terminal.
+type wizard struct {
+	in *bufio.Reader
+}
+
+// ask prints question and returns the line the user answered, or def if the
+// answer is empty.
+func (w *wizard) ask(question, def string) string {
+	if def != "" {
+		fmt.Fprintf(os.Stderr, "%s [%s]: ", question, def)
+	} else {
+		fmt.Fprintf(os.Stderr, "%s: ", question)
+	}
+	line, err := w.in.ReadString('\n')
+	if err == io.EOF && line == "" {
+		exit(ExitAborted, "\naborted")
+	}
+	if line = strings.TrimSpace(line); line == "" {
+		return def
+	}
+	return line
+}
+
+// askYesNo asks a yes/no question that defaults to no.
+func (w *wizard) askYesNo(question string) bool {
+	answer := strings.ToLower(w.ask(question+" (y/N)", ""))
+	return answer == "y" || answer == "yes"
+}
+
+// RunWizard asks the user for the target and the options of an install, and
+// returns the arguments of the eget command that performs it. target is
+// asked for if it is empty.
+func RunWizard(target string) []string {
+	w := &wizard{in: bufio.NewReader(os.Stdin)}
+	fmt.Fprintln(os.Stderr, "Answer each question, or press enter for the default in brackets.")
+	for target == "" {
+		target = w.ask("Repo (user/repo) or URL to install from", "")
+	}
+
+	args := []string{}
+	def := installDir()
+	question := "Directory to install to (--to)"
+	if def == "" {
+		question = "Directory to install to, or empty for the current one (--to)"
+	}
+	dir := w.ask(question, def)
+	name := w.ask("Name to install the binary as, if not its own", "")
+	if dir != def || name != "" {
+		args = append(args, "--to", filepath.Join(dir, name))
+	}
+	if tag := w.ask("Release tag, or empty for the latest (--tag)", ""); tag != "" {
+		args = append(args, "--tag", tag)
+	} else if w.askYesNo("Include pre-releases (--pre-release)?") {
+		args = append(args, "--pre-release")
+	}
+	filters := w.ask("Strings the asset name must contain, separated by spaces; prefix with ^ to exclude (--asset)", "")
+	for _, f := range strings.Fields(filters) {
+		args = append(args, "--asset", f)
+	}
+	if file := w.ask("Glob of the file to extract from an archive (--file)", ""); file != "" {
+		args = append(args, "--file", file)
+	}
+	return append(args, target)
+}
+
+// RunEget runs eget with args, connected to the terminal so it can ask which
+// asset to install, and returns its exit status.
+func RunEget(args []string) (int, error) {
+	binary, err := os.Executable()
+	if err != nil {
+		binary = os.Args[0]
+	}
+	if opts.Root != "" {
+		args = append([]string{"--root", opts.Root}, args...)
+	}
+	cmd := exec.Command(binary, args...)
+	cmd.Stdin = os.Stdin
+	cmd.Stdout = os.Stdout
+	cmd.Stderr = os.Stderr
+	return runForeground(cmd)
+}
+
+// plainArg matches arguments that need no quoting in a shell.
+var plainArg = regexp.MustCompile(`^[A-Za-z0-9_@%+=:,./~-]+$`)
+
+// commandLine returns the eget command with args as it would be typed in a
+// shell.
+func commandLine(args []string) string {
+	return strings.TrimSpace("eget " + joinArgs(args))
+}
+
+// joinArgs returns args as they would be typed in a shell, quoting them only
+// where needed.
+func joinArgs(args []string) string {
+	words := []string{}
+	for _, a := range args {
+		if !plainArg.MatchString(a) {
+			a = shellQuote(a)
+		}
+		words = append(words, a)
+	}
+	return strings.Join(words, " ")
+}